@@ -0,0 +1,498 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use smallvec::SmallVec;
+
+/// The number of spaces in each word of a `Bitmask`.
+const WORD_BITS: usize = u64::BITS as usize;
+
+// =============================================================================
+/// A set of spaces on a board, with bit #idx set for space #idx. Masks of
+/// boards with up to 128 spaces are kept inline, and larger boards spill over
+/// onto the heap.
+///
+/// Trailing zero words are always trimmed, so two masks holding the same
+/// spaces compare equal whatever boards they came from.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Bitmask(SmallVec<[u64; 2]>);
+
+impl Bitmask {
+    pub const EMPTY: Bitmask = Bitmask(SmallVec::new_const());
+
+    // -------------------------------------------------------------------------
+    pub fn single(idx: usize) -> Self {
+        let mut mask = Bitmask::EMPTY;
+        mask.insert(idx);
+
+        mask
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn contains(&self, idx: usize) -> bool {
+        let word = self.0.get(idx / WORD_BITS).copied().unwrap_or(0);
+
+        word & (1 << (idx % WORD_BITS)) != 0
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn insert(&mut self, idx: usize) {
+        let word = idx / WORD_BITS;
+        if word >= self.0.len() {
+            self.0.resize(word + 1, 0);
+        }
+        self.0[word] |= 1 << (idx % WORD_BITS);
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn remove(&mut self, idx: usize) {
+        if let Some(word) = self.0.get_mut(idx / WORD_BITS) {
+            *word &= !(1 << (idx % WORD_BITS));
+            self.trim();
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the spaces in `self` that are not in `other`.
+    pub fn difference(&self, other: &Bitmask) -> Self {
+        let mut mask = self.clone();
+        for (word, other_word) in mask.0.iter_mut().zip(other.0.iter()) {
+            *word &= !other_word;
+        }
+        mask.trim();
+
+        mask
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return whether `self` and `other` have any space in common.
+    pub fn intersects(&self, other: &Bitmask) -> bool {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .any(|(word, other_word)| word & other_word != 0)
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn len(&self) -> u32 {
+        self.0.iter().map(|word| word.count_ones()).sum()
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the lowest index in the set.
+    pub fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    // -------------------------------------------------------------------------
+    /// Iterate over the indices in the set, from lowest to highest.
+    pub fn iter(&self) -> Idxs<'_> {
+        Idxs {
+            words: &self.0,
+            word_idx: 0,
+            word: self.0.first().copied().unwrap_or(0),
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn to_idxs(&self) -> HashSet<usize> {
+        self.iter().collect()
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the set with every index raised by `n`.
+    fn shift_up(&self, n: usize) -> Self {
+        let (words, bits) = (n / WORD_BITS, n % WORD_BITS);
+        let mut mask = Bitmask(SmallVec::from_elem(0, words));
+        let mut carry = 0;
+        for &word in self.0.iter() {
+            mask.0.push(word << bits | carry);
+            carry = if bits == 0 {
+                0
+            } else {
+                word >> (WORD_BITS - bits)
+            };
+        }
+        mask.0.push(carry);
+        mask.trim();
+
+        mask
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the set with every index lowered by `n`, dropping the indices
+    /// below `n`.
+    fn shift_down(&self, n: usize) -> Self {
+        let (words, bits) = (n / WORD_BITS, n % WORD_BITS);
+        let kept = self.0.get(words..).unwrap_or(&[]);
+        let mut mask: Bitmask = Bitmask(
+            kept.iter()
+                .enumerate()
+                .map(|(i, &word)| {
+                    let carry = match kept.get(i + 1) {
+                        Some(next) if bits != 0 => next << (WORD_BITS - bits),
+                        _ => 0,
+                    };
+                    word >> bits | carry
+                })
+                .collect(),
+        );
+        mask.trim();
+
+        mask
+    }
+
+    // -------------------------------------------------------------------------
+    fn trim(&mut self) {
+        while self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+    }
+}
+
+/// Implement a bitwise operator for every mix of owned and borrowed masks,
+/// reusing the left-hand mask when it is owned.
+macro_rules! impl_bit_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident) => {
+        impl $op_assign<&Bitmask> for Bitmask {
+            fn $method_assign(&mut self, other: &Bitmask) {
+                let len = self.0.len().max(other.0.len());
+                self.0.resize(len, 0);
+                for (i, word) in self.0.iter_mut().enumerate() {
+                    let other_word = other.0.get(i).copied().unwrap_or(0);
+                    $op_assign::$method_assign(word, other_word);
+                }
+                self.trim();
+            }
+        }
+
+        impl $op_assign for Bitmask {
+            fn $method_assign(&mut self, other: Bitmask) {
+                $op_assign::$method_assign(self, &other);
+            }
+        }
+
+        impl $op<&Bitmask> for Bitmask {
+            type Output = Bitmask;
+
+            fn $method(mut self, other: &Bitmask) -> Bitmask {
+                $op_assign::$method_assign(&mut self, other);
+                self
+            }
+        }
+
+        impl $op for Bitmask {
+            type Output = Bitmask;
+
+            fn $method(self, other: Bitmask) -> Bitmask {
+                $op::$method(self, &other)
+            }
+        }
+
+        impl $op for &Bitmask {
+            type Output = Bitmask;
+
+            fn $method(self, other: &Bitmask) -> Bitmask {
+                $op::$method(self.clone(), other)
+            }
+        }
+
+        impl $op<Bitmask> for &Bitmask {
+            type Output = Bitmask;
+
+            fn $method(self, other: Bitmask) -> Bitmask {
+                $op::$method(other, self)
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+
+impl FromIterator<usize> for Bitmask {
+    fn from_iter<I: IntoIterator<Item = usize>>(idxs: I) -> Self {
+        let mask = idxs.into_iter().fold(Bitmask::EMPTY, |mut mask, idx| {
+            mask.insert(idx);
+            mask
+        });
+
+        mask
+    }
+}
+
+impl From<&HashSet<usize>> for Bitmask {
+    fn from(idxs: &HashSet<usize>) -> Self {
+        idxs.iter().copied().collect()
+    }
+}
+
+// =============================================================================
+/// An iterator over the indices in a `Bitmask`.
+pub struct Idxs<'a> {
+    words: &'a [u64],
+    word_idx: usize,
+    /// The bits of the current word that have not been visited yet.
+    word: u64,
+}
+
+impl Iterator for Idxs<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.word == 0 {
+            self.word_idx += 1;
+            self.word = *self.words.get(self.word_idx)?;
+        }
+        let idx =
+            self.word_idx * WORD_BITS + self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1;
+
+        Some(idx)
+    }
+}
+
+/// The geometry of each board size made so far, keyed by rows and columns.
+type GeometryCache = HashMap<(usize, usize), Arc<Geometry>>;
+
+// =============================================================================
+/// The masks that depend only on the size of a board, computed once for each
+/// size and shared by every board of that size.
+pub struct Geometry {
+    cols: usize,
+    all: Bitmask,
+    rows: Vec<Bitmask>,
+    columns: Vec<Bitmask>,
+    adjacent: Vec<Bitmask>,
+    surrounding: Vec<Bitmask>,
+    corners: Bitmask,
+    center: Option<Bitmask>,
+}
+
+impl Geometry {
+    /// Return the masks of a board with `rows` rows and `cols` columns,
+    /// computing them only the first time that size is asked for.
+    pub fn shared(rows: usize, cols: usize) -> Arc<Geometry> {
+        static GEOMETRIES: OnceLock<Mutex<GeometryCache>> = OnceLock::new();
+
+        let mut geometries = GEOMETRIES
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .unwrap();
+        let geometry = geometries
+            .entry((rows, cols))
+            .or_insert_with(|| Arc::new(Geometry::new(rows, cols)));
+
+        Arc::clone(geometry)
+    }
+
+    // -------------------------------------------------------------------------
+    /// Compute the masks of a board with `rows` rows and `cols` columns.
+    fn new(rows: usize, cols: usize) -> Self {
+        let elems = rows * cols;
+        let idx = |row: usize, col: usize| row * cols + col;
+
+        let row_masks: Vec<Bitmask> = (0..rows)
+            .map(|row| (0..cols).map(|col| idx(row, col)).collect())
+            .collect();
+        let col_masks: Vec<Bitmask> = (0..cols)
+            .map(|col| (0..rows).map(|row| idx(row, col)).collect())
+            .collect();
+
+        let neighbors = |diagonal: bool| -> Vec<Bitmask> {
+            (0..elems)
+                .map(|i| {
+                    let (row, col) = ((i / cols) as isize, (i % cols) as isize);
+                    let mut mask = Bitmask::EMPTY;
+                    for (d_row, d_col) in [
+                        (-1, -1),
+                        (-1, 0),
+                        (-1, 1),
+                        (0, -1),
+                        (0, 1),
+                        (1, -1),
+                        (1, 0),
+                        (1, 1),
+                    ] {
+                        let is_diagonal = d_row != 0 && d_col != 0;
+                        let (r, c) = (row + d_row, col + d_col);
+                        if (diagonal || !is_diagonal)
+                            && (0..rows as isize).contains(&r)
+                            && (0..cols as isize).contains(&c)
+                        {
+                            mask.insert(idx(r as usize, c as usize));
+                        }
+                    }
+                    mask
+                })
+                .collect()
+        };
+
+        let corners =
+            Bitmask::from_iter([0, cols - 1, elems - cols, elems - 1]);
+        let center = if rows.is_multiple_of(2) && cols.is_multiple_of(2) {
+            let southeast_center = idx(rows / 2, cols / 2);
+            Some(Bitmask::from_iter([
+                southeast_center - cols - 1,
+                southeast_center - cols,
+                southeast_center - 1,
+                southeast_center,
+            ]))
+        } else {
+            None
+        };
+
+        let geometry = Self {
+            cols,
+            all: (0..elems).collect(),
+            rows: row_masks,
+            columns: col_masks,
+            adjacent: neighbors(false),
+            surrounding: neighbors(true),
+            corners,
+            center,
+        };
+
+        geometry
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn all(&self) -> &Bitmask {
+        &self.all
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn row(&self, row: usize) -> &Bitmask {
+        &self.rows[row]
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn col(&self, col: usize) -> &Bitmask {
+        &self.columns[col]
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn adjacent(&self, idx: usize) -> &Bitmask {
+        &self.adjacent[idx]
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn surrounding(&self, idx: usize) -> &Bitmask {
+        &self.surrounding[idx]
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn corners(&self) -> &Bitmask {
+        &self.corners
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the four center spaces, or `None` if the board has an odd number
+    /// of rows or columns.
+    pub fn center(&self) -> Option<&Bitmask> {
+        self.center.as_ref()
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return `mask` together with every space adjacent to it, by shifting
+    /// the whole mask one space in each direction.
+    pub fn spread(&self, mask: &Bitmask) -> Bitmask {
+        let first_col = self.col(0);
+        let last_col = self.col(self.cols - 1);
+        let west = mask.difference(first_col).shift_down(1);
+        let east = mask.difference(last_col).shift_up(1);
+        let north = mask.shift_down(self.cols);
+        let south = mask.shift_up(self.cols);
+        let spread = (mask | west | east | north | south) & &self.all;
+
+        spread
+    }
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_bitmask() {
+        let mut mask = Bitmask::from_iter([3, 0, 127, 200]);
+        assert_eq!(mask.len(), 4);
+        assert!(mask.contains(127));
+        assert!(!mask.contains(500));
+        assert_eq!(mask.iter().collect::<Vec<usize>>(), [0, 3, 127, 200]);
+        mask.remove(0);
+        assert_eq!(mask.first(), Some(3));
+        assert_eq!(
+            mask.difference(&Bitmask::from_iter([3, 200])),
+            Bitmask::single(127)
+        );
+        assert!(mask.intersects(&Bitmask::single(200)));
+        assert!(!mask.intersects(&Bitmask::single(4)));
+        assert!(Bitmask::EMPTY.is_empty());
+        assert_eq!(Bitmask::EMPTY.first(), None);
+
+        // Emptying the high words leaves a mask equal to one that never had
+        // them.
+        mask.remove(200);
+        assert_eq!(mask, Bitmask::from_iter([3, 127]));
+        assert!((&mask & &Bitmask::single(300)).is_empty());
+        assert_eq!(mask.shift_up(70), Bitmask::from_iter([73, 197]));
+        assert_eq!(mask.shift_down(64), Bitmask::single(63));
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_geometry() {
+        let geometry = Geometry::new(3, 4);
+        assert_eq!(geometry.adjacent(0), &Bitmask::from_iter([1, 4]));
+        assert_eq!(geometry.adjacent(5), &Bitmask::from_iter([1, 4, 6, 9]));
+        assert_eq!(geometry.surrounding(3), &Bitmask::from_iter([2, 6, 7]));
+        assert_eq!(geometry.corners(), &Bitmask::from_iter([0, 3, 8, 11]));
+        assert_eq!(geometry.center(), None);
+        assert_eq!(geometry.row(1), &Bitmask::from_iter([4, 5, 6, 7]));
+        assert_eq!(geometry.col(3), &Bitmask::from_iter([3, 7, 11]));
+
+        // Spreading never wraps around the edge of a row.
+        assert_eq!(
+            geometry.spread(&Bitmask::single(3)),
+            Bitmask::from_iter([2, 3, 7])
+        );
+        assert_eq!(
+            geometry.spread(&Bitmask::single(4)),
+            Bitmask::from_iter([0, 4, 5, 8])
+        );
+
+        assert_eq!(
+            Geometry::new(4, 4).center(),
+            Some(&Bitmask::from_iter([5, 6, 9, 10]))
+        );
+
+        // Boards of the same size share their masks.
+        assert!(Arc::ptr_eq(
+            &Geometry::shared(5, 7),
+            &Geometry::shared(5, 7)
+        ));
+        assert!(!Arc::ptr_eq(
+            &Geometry::shared(5, 7),
+            &Geometry::shared(7, 5)
+        ));
+
+        // Spreading crosses the boundary between words.
+        let geometry = Geometry::new(12, 12);
+        assert_eq!(
+            geometry.spread(&Bitmask::single(64)),
+            Bitmask::from_iter([52, 63, 64, 65, 76])
+        );
+        assert_eq!(
+            geometry.spread(&Bitmask::single(143)),
+            Bitmask::from_iter([131, 142, 143])
+        );
+    }
+}
@@ -0,0 +1,447 @@
+use std::error;
+use std::fmt;
+
+use crate::board::pattern::PatternMatch;
+use crate::board::space::{BuildingType, Resource, Space};
+use crate::board::Board;
+use crate::building_config::{BlackBuilding, BuildingConfig};
+use crate::error::Error;
+
+// =============================================================================
+/// Reasons a construction may be refused.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConstructError {
+    /// The chosen building does not match the building the pattern builds.
+    WrongBuildingType {
+        expected: BuildingType,
+        found: BuildingType,
+    },
+    /// The target space is not one of the spaces covered by the pattern.
+    IdxNotInPattern(usize),
+    /// The pattern is not currently formed by resources on the board.
+    PatternNotOnBoard,
+    /// The target space holds a Trading Post, which stays where it is.
+    TargetIsTradingPost(usize),
+    /// The town already has a monument, and may only have one.
+    MonumentAlreadyBuilt,
+}
+
+impl fmt::Display for ConstructError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConstructError::WrongBuildingType { expected, found } => write!(
+                f,
+                "pattern builds a {expected:?} building, not a {found:?} one"
+            ),
+            ConstructError::IdxNotInPattern(idx) => {
+                write!(f, "space #{idx} is not part of the pattern")
+            }
+            ConstructError::PatternNotOnBoard => {
+                write!(f, "pattern is not formed on the board")
+            }
+            ConstructError::TargetIsTradingPost(idx) => {
+                write!(f, "space #{idx} holds a trading post")
+            }
+            ConstructError::MonumentAlreadyBuilt => {
+                write!(f, "the town already has a monument")
+            }
+        }
+    }
+}
+
+impl error::Error for ConstructError {}
+
+// =============================================================================
+impl BuildingConfig {
+    /// Return the space a newly constructed building of type `building_type`
+    /// occupies. Factories and banks have yet to be assigned a resource, and
+    /// warehouses start out empty.
+    pub fn new_building_space(&self, building_type: BuildingType) -> Space {
+        let space = match building_type {
+            BuildingType::Black => match self.black() {
+                BlackBuilding::Bank | BlackBuilding::Factory => {
+                    Space::BuildingWithOptResource(building_type, None)
+                }
+                BlackBuilding::TradingPost => Space::Building(building_type),
+                BlackBuilding::Warehouse => {
+                    Space::BuildingWithResources(building_type, Vec::new())
+                }
+            },
+            _ => Space::Building(building_type),
+        };
+
+        space
+    }
+}
+
+// =============================================================================
+impl Board {
+    /// Return `true` if the resources described by `pattern_match` currently
+    /// form the pattern of its building.
+    pub fn is_pattern_on_board(
+        &self,
+        building_config: &BuildingConfig,
+        pattern_match: &PatternMatch,
+    ) -> bool {
+        let is_on_board = building_config
+            .patterns()
+            .get(&pattern_match.building_type())
+            .map(|pattern| {
+                self.find_pattern_with_wilds(
+                    pattern,
+                    &self.wild_idxs(building_config),
+                )
+                .contains(pattern_match.idxs())
+            })
+            .unwrap_or(false);
+
+        is_on_board
+    }
+
+    // -------------------------------------------------------------------------
+    /// Construct a building of type `building_type` on space #idx from the
+    /// resources in `pattern_match`. The other resources in the pattern are
+    /// returned to the supply, leaving their spaces empty. Trading Posts used
+    /// in the pattern stay in place.
+    pub fn construct(
+        &mut self,
+        building_config: &BuildingConfig,
+        pattern_match: &PatternMatch,
+        idx: usize,
+        building_type: BuildingType,
+    ) -> Result<(), ConstructError> {
+        self.construct_on(
+            building_config,
+            pattern_match,
+            idx,
+            building_type,
+            false,
+        )
+    }
+
+    // -------------------------------------------------------------------------
+    /// Like `construct`, but the building may also go on any empty space
+    /// outside of the pattern.
+    pub fn construct_anywhere(
+        &mut self,
+        building_config: &BuildingConfig,
+        pattern_match: &PatternMatch,
+        idx: usize,
+        building_type: BuildingType,
+    ) -> Result<(), ConstructError> {
+        self.construct_on(
+            building_config,
+            pattern_match,
+            idx,
+            building_type,
+            true,
+        )
+    }
+
+    // -------------------------------------------------------------------------
+    fn construct_on(
+        &mut self,
+        building_config: &BuildingConfig,
+        pattern_match: &PatternMatch,
+        idx: usize,
+        building_type: BuildingType,
+        anywhere: bool,
+    ) -> Result<(), ConstructError> {
+        if pattern_match.building_type() != building_type {
+            return Err(ConstructError::WrongBuildingType {
+                expected: pattern_match.building_type(),
+                found: building_type,
+            });
+        }
+        let in_pattern = pattern_match.idxs().contains(&idx);
+        let on_empty_space =
+            anywhere && self.spaces.get(idx) == Some(&Space::Empty);
+        if !in_pattern && !on_empty_space {
+            return Err(ConstructError::IdxNotInPattern(idx));
+        }
+        if !self.is_pattern_on_board(building_config, pattern_match) {
+            return Err(ConstructError::PatternNotOnBoard);
+        }
+        if building_type == BuildingType::Magenta
+            && self.count_building_type(BuildingType::Magenta) > 0
+        {
+            return Err(ConstructError::MonumentAlreadyBuilt);
+        }
+        let wild_idxs = self.wild_idxs(building_config);
+        if wild_idxs.contains(&idx) {
+            return Err(ConstructError::TargetIsTradingPost(idx));
+        }
+
+        for pattern_idx in pattern_match.idxs() {
+            if !wild_idxs.contains(pattern_idx) {
+                self.remove(*pattern_idx);
+            }
+        }
+        self.set_space(idx, building_config.new_building_space(building_type));
+        self.record_buildings_when_built(idx);
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Put `resource` on the Factory or Bank on space #idx.
+    pub fn assign_resource(&mut self, idx: usize, resource: Resource) {
+        self.try_assign_resource(idx, resource).unwrap()
+    }
+
+    // -------------------------------------------------------------------------
+    /// Put `resource` on the Factory or Bank on space #idx, or return an error
+    /// if there is no building there waiting for a resource.
+    pub fn try_assign_resource(
+        &mut self,
+        idx: usize,
+        resource: Resource,
+    ) -> Result<(), Error> {
+        let space = self.spaces.get(idx).ok_or(Error::IdxOutOfRange(idx))?;
+        if *space != Space::BuildingWithOptResource(BuildingType::Black, None) {
+            return Err(Error::NotAwaitingResource(idx));
+        }
+        self.set_space(
+            idx,
+            Space::BuildingWithOptResource(BuildingType::Black, Some(resource)),
+        );
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Replace the resources stored on the Warehouse on space #idx.
+    pub fn store_resources(&mut self, idx: usize, resources: Vec<Resource>) {
+        self.try_store_resources(idx, resources).unwrap()
+    }
+
+    // -------------------------------------------------------------------------
+    /// Replace the resources stored on the Warehouse on space #idx, or return
+    /// an error if there is no building there that stores resources.
+    pub fn try_store_resources(
+        &mut self,
+        idx: usize,
+        resources: Vec<Resource>,
+    ) -> Result<(), Error> {
+        let space = self.spaces.get(idx).ok_or(Error::IdxOutOfRange(idx))?;
+        if space.resources().is_none() {
+            return Err(Error::NotAWarehouse(idx));
+        }
+        self.set_space(
+            idx,
+            Space::BuildingWithResources(BuildingType::Black, resources),
+        );
+
+        Ok(())
+    }
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::board::space::Resource;
+    use crate::building_config::{
+        BlueBuilding, GrayBuilding, GreenBuilding, MagentaBuilding,
+        OrangeBuilding, RedBuilding, YellowBuilding,
+    };
+
+    // -------------------------------------------------------------------------
+    fn config_with_black(black: BlackBuilding) -> BuildingConfig {
+        BuildingConfig::new(
+            black,
+            BlueBuilding::Cottage,
+            GrayBuilding::Well,
+            GreenBuilding::Tavern,
+            MagentaBuilding::SilvaForum,
+            OrangeBuilding::Chapel,
+            RedBuilding::Farm,
+            YellowBuilding::Tailor,
+        )
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_new_building_space() {
+        let building_config = config_with_black(BlackBuilding::Bank);
+        assert_eq!(
+            building_config.new_building_space(BuildingType::Blue),
+            Space::Building(BuildingType::Blue)
+        );
+        assert_eq!(
+            building_config.new_building_space(BuildingType::Black),
+            Space::BuildingWithOptResource(BuildingType::Black, None)
+        );
+
+        let building_config = config_with_black(BlackBuilding::TradingPost);
+        assert_eq!(
+            building_config.new_building_space(BuildingType::Black),
+            Space::Building(BuildingType::Black)
+        );
+
+        let building_config = config_with_black(BlackBuilding::Warehouse);
+        assert_eq!(
+            building_config.new_building_space(BuildingType::Black),
+            Space::BuildingWithResources(BuildingType::Black, Vec::new())
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_construct() {
+        let building_config = config_with_black(BlackBuilding::Warehouse);
+        let mut board = Board::new(4, 4);
+        board.place(0, Resource::Wood);
+        board.place(1, Resource::Stone);
+        board.place(2, Resource::Wood);
+        let well = PatternMatch::new(BuildingType::Gray, HashSet::from([0, 1]));
+
+        // Wrong building for the pattern.
+        assert_eq!(
+            board.construct(&building_config, &well, 0, BuildingType::Blue),
+            Err(ConstructError::WrongBuildingType {
+                expected: BuildingType::Gray,
+                found: BuildingType::Blue,
+            })
+        );
+
+        // Target outside of the pattern.
+        assert_eq!(
+            board.construct(&building_config, &well, 2, BuildingType::Gray),
+            Err(ConstructError::IdxNotInPattern(2))
+        );
+
+        // Pattern that is not on the board.
+        let not_a_well =
+            PatternMatch::new(BuildingType::Gray, HashSet::from([1, 5]));
+        assert_eq!(
+            board.construct(
+                &building_config,
+                &not_a_well,
+                1,
+                BuildingType::Gray
+            ),
+            Err(ConstructError::PatternNotOnBoard)
+        );
+        assert_eq!(board.spaces()[1], Space::Resource(Resource::Stone));
+
+        // A legal construction clears the other resources.
+        assert!(board
+            .construct(&building_config, &well, 1, BuildingType::Gray)
+            .is_ok());
+        assert_eq!(board.spaces()[0], Space::Empty);
+        assert_eq!(board.spaces()[1], Space::Building(BuildingType::Gray));
+        assert_eq!(board.spaces()[2], Space::Resource(Resource::Wood));
+
+        // The resources are gone, so the pattern cannot be used again.
+        assert_eq!(
+            board.construct(&building_config, &well, 0, BuildingType::Gray),
+            Err(ConstructError::PatternNotOnBoard)
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_construct_warehouse() {
+        let building_config = config_with_black(BlackBuilding::Warehouse);
+        let mut board = Board::new(4, 4);
+        board.place(0, Resource::Wheat);
+        board.place(1, Resource::Wood);
+        board.place(2, Resource::Wheat);
+        board.place(4, Resource::Brick);
+        board.place(6, Resource::Brick);
+        let matches = board.matching_patterns(&building_config);
+        assert_eq!(matches.len(), 1);
+
+        assert!(board
+            .construct(&building_config, &matches[0], 6, BuildingType::Black)
+            .is_ok());
+        assert_eq!(
+            board.spaces()[6],
+            Space::BuildingWithResources(BuildingType::Black, Vec::new())
+        );
+        let unused = board.spaces().iter().filter(|s| s.is_unused()).count();
+        assert_eq!(unused, 15);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_construct_second_monument() {
+        let building_config = config_with_black(BlackBuilding::Warehouse);
+        let mut board = Board::new(4, 4);
+        board.place(0, Resource::Brick);
+        board.place(4, Resource::Wood);
+        board.place(5, Resource::Wheat);
+        board.place(6, Resource::Wood);
+        board.place(15, BuildingType::Magenta);
+        let silva_forum = PatternMatch::new(
+            BuildingType::Magenta,
+            HashSet::from([0, 4, 5, 6]),
+        );
+        assert_eq!(
+            board.construct(
+                &building_config,
+                &silva_forum,
+                0,
+                BuildingType::Magenta
+            ),
+            Err(ConstructError::MonumentAlreadyBuilt)
+        );
+        assert!(board.matching_patterns(&building_config).is_empty());
+
+        board.remove(15);
+        assert!(board
+            .construct(&building_config, &silva_forum, 0, BuildingType::Magenta)
+            .is_ok());
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_construct_with_trading_post() {
+        let building_config = config_with_black(BlackBuilding::TradingPost);
+        let mut board = Board::new(4, 4);
+        board.place(0, BuildingType::Black);
+        board.place(1, Resource::Stone);
+        let well = PatternMatch::new(BuildingType::Gray, HashSet::from([0, 1]));
+        assert!(board.is_pattern_on_board(&building_config, &well));
+
+        // Trading posts are only wild when they are in play.
+        let other_config = config_with_black(BlackBuilding::Warehouse);
+        assert!(!board.is_pattern_on_board(&other_config, &well));
+
+        assert_eq!(
+            board.construct(&building_config, &well, 0, BuildingType::Gray),
+            Err(ConstructError::TargetIsTradingPost(0))
+        );
+        assert!(board
+            .construct(&building_config, &well, 1, BuildingType::Gray)
+            .is_ok());
+        assert_eq!(board.spaces()[0], Space::Building(BuildingType::Black));
+        assert_eq!(board.spaces()[1], Space::Building(BuildingType::Gray));
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_try_assign_resource() {
+        let mut board = Board::new(4, 4);
+        board.place(0, (BuildingType::Black, None));
+        board.place(1, (BuildingType::Black, vec![], 3));
+        assert_eq!(board.try_assign_resource(0, Resource::Wood), Ok(()));
+        assert_eq!(
+            board.try_assign_resource(0, Resource::Wood),
+            Err(Error::NotAwaitingResource(0))
+        );
+        assert_eq!(
+            board.try_assign_resource(16, Resource::Wood),
+            Err(Error::IdxOutOfRange(16))
+        );
+
+        assert_eq!(board.try_store_resources(1, vec![Resource::Wood]), Ok(()));
+        assert_eq!(
+            board.try_store_resources(0, vec![Resource::Wood]),
+            Err(Error::NotAWarehouse(0))
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use crate::board::Board;
+use crate::error::Error;
+
+// =============================================================================
+impl Board {
+    /// Return the number of buildings in the town, including itself, when the
+    /// building on space #idx was constructed. Buildings placed directly
+    /// rather than constructed have no history.
+    pub fn buildings_when_built(&self, idx: usize) -> Option<u32> {
+        self.buildings_when_built.get(&idx).copied()
+    }
+
+    // -------------------------------------------------------------------------
+    /// Record the number of buildings currently in the town against the
+    /// building on space #idx.
+    pub fn record_buildings_when_built(&mut self, idx: usize) {
+        self.try_record_buildings_when_built(idx).unwrap()
+    }
+
+    // -------------------------------------------------------------------------
+    /// Record the number of buildings currently in the town against the
+    /// building on space #idx, or return an error if there is no building
+    /// there.
+    pub fn try_record_buildings_when_built(
+        &mut self,
+        idx: usize,
+    ) -> Result<(), Error> {
+        self.check_building(idx)?;
+        let num_buildings = self
+            .spaces
+            .iter()
+            .filter(|space| space.building_type().is_some())
+            .count() as u32;
+        self.buildings_when_built.insert(idx, num_buildings);
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Set the number of buildings in the town when the building on space
+    /// #idx was constructed, for towns written down after the fact.
+    pub fn set_buildings_when_built(&mut self, idx: usize, num_buildings: u32) {
+        self.try_set_buildings_when_built(idx, num_buildings)
+            .unwrap()
+    }
+
+    // -------------------------------------------------------------------------
+    /// Set the number of buildings in the town when the building on space
+    /// #idx was constructed, or return an error if there is no building there.
+    pub fn try_set_buildings_when_built(
+        &mut self,
+        idx: usize,
+        num_buildings: u32,
+    ) -> Result<(), Error> {
+        self.check_building(idx)?;
+        self.buildings_when_built.insert(idx, num_buildings);
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    fn check_building(&self, idx: usize) -> Result<(), Error> {
+        let space = self.spaces.get(idx).ok_or(Error::IdxOutOfRange(idx))?;
+        if space.building_type().is_none() {
+            return Err(Error::NotABuilding(idx));
+        }
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the 1-based position in which this town was completed, or
+    /// `None` if it is still being built.
+    pub fn finish_position(&self) -> Option<usize> {
+        self.finish_position
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn set_finish_position(&mut self, finish_position: Option<usize>) {
+        self.finish_position = finish_position;
+    }
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::space::BuildingType;
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_buildings_when_built() {
+        let mut board = Board::new(4, 4);
+        board.place(0, BuildingType::Blue);
+        board.place(1, BuildingType::Blue);
+        assert_eq!(board.buildings_when_built(1), None);
+
+        board.place(2, BuildingType::Magenta);
+        board.record_buildings_when_built(2);
+        assert_eq!(board.buildings_when_built(2), Some(3));
+
+        // Later buildings do not change the history.
+        board.place(3, BuildingType::Blue);
+        assert_eq!(board.buildings_when_built(2), Some(3));
+
+        board.remove(2);
+        assert_eq!(board.buildings_when_built(2), None);
+
+        board.place(2, BuildingType::Magenta);
+        board.set_buildings_when_built(2, 7);
+        assert_eq!(board.buildings_when_built(2), Some(7));
+
+        assert_eq!(
+            board.try_set_buildings_when_built(5, 7),
+            Err(Error::NotABuilding(5))
+        );
+        assert_eq!(
+            board.try_record_buildings_when_built(16),
+            Err(Error::IdxOutOfRange(16))
+        );
+    }
+}
@@ -1,29 +1,98 @@
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
+use serde::{Deserialize, Serialize};
+use strum::{EnumCount, IntoEnumIterator};
+
+use crate::board::bitboard::{Bitmask, Geometry};
 use crate::board::space::{BuildingType, Place, Space};
+use crate::error::Error;
 
+pub mod bitboard;
+pub mod construct;
+pub mod history;
+pub mod notation;
+pub mod pattern;
 pub mod space;
+pub mod validate;
 
 // =============================================================================
+#[derive(Deserialize, Serialize)]
+#[serde(try_from = "BoardData")]
 pub struct Board {
     rows: usize,
     cols: usize,
+    #[serde(skip_serializing)]
     elems: usize,
     spaces: Vec<Space>,
+    buildings_when_built: HashMap<usize, u32>,
+    finish_position: Option<usize>,
+    #[serde(skip_serializing)]
+    geometry: Arc<Geometry>,
+    /// The spaces holding each building type, indexed by `BuildingType`.
+    #[serde(skip_serializing)]
+    building_masks: [Bitmask; BuildingType::COUNT],
+}
+
+/// The serialized form of a `Board`, which is checked before it becomes one.
+#[derive(Deserialize)]
+struct BoardData {
+    rows: usize,
+    cols: usize,
+    spaces: Vec<Space>,
+    #[serde(default)]
+    buildings_when_built: HashMap<usize, u32>,
+    #[serde(default)]
+    finish_position: Option<usize>,
+}
+
+impl TryFrom<BoardData> for Board {
+    type Error = Error;
+
+    fn try_from(board_data: BoardData) -> Result<Self, Self::Error> {
+        let mut board = Board::try_new(board_data.rows, board_data.cols)?;
+        if board_data.spaces.len() != board.elems {
+            return Err(Error::WrongNumberOfSpaces {
+                expected: board.elems,
+                found: board_data.spaces.len(),
+            });
+        }
+        for (idx, space) in board_data.spaces.into_iter().enumerate() {
+            board.set_space(idx, space);
+        }
+        board.buildings_when_built = board_data.buildings_when_built;
+        board.finish_position = board_data.finish_position;
+
+        Ok(board)
+    }
 }
 
 impl Board {
     pub fn new(rows: usize, cols: usize) -> Self {
-        assert!(rows > 2);
-        assert!(cols > 2);
+        Self::try_new(rows, cols).unwrap()
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return an empty board, or an error if it has fewer than three rows or
+    /// columns.
+    pub fn try_new(rows: usize, cols: usize) -> Result<Self, Error> {
+        if rows <= 2 || cols <= 2 {
+            return Err(Error::BoardTooSmall { rows, cols });
+        }
         let elems = rows * cols;
         let spaces = vec![Space::Empty; elems];
-        Self {
+        let board = Self {
             rows,
             cols,
             elems,
             spaces,
-        }
+            buildings_when_built: HashMap::new(),
+            finish_position: None,
+            geometry: Geometry::shared(rows, cols),
+            building_masks: Default::default(),
+        };
+
+        Ok(board)
     }
 
     // -------------------------------------------------------------------------
@@ -57,95 +126,183 @@ impl Board {
     }
 
     // -------------------------------------------------------------------------
-    // Methods that return a HashSet of indices.
+    // Methods that return a Bitmask of indices.
     // -------------------------------------------------------------------------
-    pub fn adjacent_idxs(&self, idx: usize) -> HashSet<usize> {
-        let mut adjacent_idxs = HashSet::new();
+    pub fn all_mask(&self) -> &Bitmask {
+        self.geometry.all()
+    }
 
-        // Northern neighbor.
-        if self.row(idx) > 0 {
-            adjacent_idxs.insert(idx - self.cols);
-        }
+    // -------------------------------------------------------------------------
+    pub fn row_mask(&self, row: usize) -> &Bitmask {
+        self.geometry.row(row)
+    }
 
-        // Western neighbor.
-        if self.col(idx) > 0 {
-            adjacent_idxs.insert(idx - 1);
-        }
+    // -------------------------------------------------------------------------
+    pub fn col_mask(&self, col: usize) -> &Bitmask {
+        self.geometry.col(col)
+    }
 
-        // Eastern neighbor.
-        if self.col(idx) < self.cols - 1 {
-            adjacent_idxs.insert(idx + 1);
-        }
+    // -------------------------------------------------------------------------
+    pub fn adjacent_mask(&self, idx: usize) -> &Bitmask {
+        self.geometry.adjacent(idx)
+    }
 
-        // Southern neighbor.
-        if self.row(idx) < self.rows - 1 {
-            adjacent_idxs.insert(idx + self.cols);
-        }
+    // -------------------------------------------------------------------------
+    pub fn surrounding_mask(&self, idx: usize) -> &Bitmask {
+        self.geometry.surrounding(idx)
+    }
 
-        adjacent_idxs
+    // -------------------------------------------------------------------------
+    pub fn corner_mask(&self) -> &Bitmask {
+        self.geometry.corners()
     }
 
     // -------------------------------------------------------------------------
-    fn diagonal_idxs(&self, idx: usize) -> HashSet<usize> {
-        let mut diagonal_idxs = HashSet::new();
+    pub fn center_mask(&self) -> &Bitmask {
+        self.try_center_mask().unwrap()
+    }
 
-        // Northwestern neighbor.
-        if self.row(idx) > 0 && self.col(idx) > 0 {
-            diagonal_idxs.insert(idx - self.cols - 1);
-        }
+    // -------------------------------------------------------------------------
+    /// Return the four center spaces, or an error if the board has an odd
+    /// number of rows or columns.
+    pub fn try_center_mask(&self) -> Result<&Bitmask, Error> {
+        self.geometry.center().ok_or(Error::NoCenter {
+            rows: self.rows,
+            cols: self.cols,
+        })
+    }
 
-        // Northeastern neighbor.
-        if self.row(idx) > 0 && self.col(idx) < self.cols - 1 {
-            diagonal_idxs.insert(idx - self.cols + 1);
-        }
+    // -------------------------------------------------------------------------
+    /// Return the spaces with a building of type `building_type`.
+    pub fn building_mask(&self, building_type: BuildingType) -> &Bitmask {
+        &self.building_masks[building_type as usize]
+    }
 
-        // Southwestern neighbor.
-        if self.row(idx) < self.rows - 1 && self.col(idx) > 0 {
-            diagonal_idxs.insert(idx + self.cols - 1);
+    // -------------------------------------------------------------------------
+    /// Return the spaces with a building of any type in `building_types`.
+    pub fn building_types_mask(
+        &self,
+        building_types: &HashSet<BuildingType>,
+    ) -> Bitmask {
+        let mask = building_types
+            .iter()
+            .fold(Bitmask::EMPTY, |mask, building_type| {
+                mask | self.building_mask(*building_type)
+            });
+
+        mask
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the spaces with a building of any type.
+    pub fn buildings_mask(&self) -> Bitmask {
+        let mask = self
+            .building_masks
+            .iter()
+            .fold(Bitmask::EMPTY, |mask, building_mask| mask | building_mask);
+
+        mask
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the spaces with no building, which may hold a resource.
+    pub fn unused_mask(&self) -> Bitmask {
+        self.all_mask().difference(&self.buildings_mask())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return `mask` together with every space adjacent to it.
+    pub fn spread_mask(&self, mask: &Bitmask) -> Bitmask {
+        self.geometry.spread(mask)
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the group of spaces in `mask` that are connected to space #idx,
+    /// which must be in `mask`.
+    fn contiguous_group(&self, mask: &Bitmask, idx: usize) -> Bitmask {
+        let mut group = Bitmask::single(idx);
+        loop {
+            let grown = self.spread_mask(&group) & mask;
+            if grown == group {
+                break;
+            }
+            group = grown;
         }
 
-        // Southeastern neighbor.
-        if self.row(idx) < self.rows - 1 && self.col(idx) < self.cols - 1 {
-            diagonal_idxs.insert(idx + self.cols + 1);
+        group
+    }
+
+    // -------------------------------------------------------------------------
+    /// Split `mask` into its groups of adjacent spaces, in order of their
+    /// lowest index.
+    pub fn contiguous_group_masks(&self, mask: &Bitmask) -> Vec<Bitmask> {
+        let mut mask = mask.clone();
+        let mut groups = Vec::new();
+        while let Some(idx) = mask.first() {
+            let group = self.contiguous_group(&mask, idx);
+            mask = mask.difference(&group);
+            groups.push(group);
         }
 
-        diagonal_idxs
+        groups
     }
 
     // -------------------------------------------------------------------------
-    pub fn surrounding_idxs(&self, idx: usize) -> HashSet<usize> {
-        let mut surrounding_idxs = self.adjacent_idxs(idx);
-        surrounding_idxs.extend(self.diagonal_idxs(idx));
+    /// Return the set of different building types in the spaces of `mask`.
+    pub fn unique_building_types_in_mask(
+        &self,
+        mask: &Bitmask,
+    ) -> HashSet<BuildingType> {
+        let unique_building_types = BuildingType::iter()
+            .filter(|building_type| {
+                self.building_mask(*building_type).intersects(mask)
+            })
+            .collect();
+
+        unique_building_types
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the number of different building types in the spaces of `mask`.
+    pub fn count_unique_building_types_in_mask(&self, mask: &Bitmask) -> usize {
+        let count = self
+            .building_masks
+            .iter()
+            .filter(|building_mask| building_mask.intersects(mask))
+            .count();
 
-        surrounding_idxs
+        count
+    }
+
+    // -------------------------------------------------------------------------
+    // Methods that return a HashSet of indices.
+    // -------------------------------------------------------------------------
+    pub fn adjacent_idxs(&self, idx: usize) -> HashSet<usize> {
+        self.adjacent_mask(idx).to_idxs()
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn surrounding_idxs(&self, idx: usize) -> HashSet<usize> {
+        self.surrounding_mask(idx).to_idxs()
     }
 
     // -------------------------------------------------------------------------
     pub fn center_idxs(&self) -> HashSet<usize> {
-        assert!(self.rows % 2 == 0);
-        assert!(self.cols % 2 == 0);
-
-        let southeast_center = self.rows / 2 * self.cols + self.cols / 2;
-        let center_idxs = HashSet::from([
-            southeast_center - self.cols - 1,
-            southeast_center - self.cols,
-            southeast_center - 1,
-            southeast_center,
-        ]);
+        self.try_center_idxs().unwrap()
+    }
 
-        center_idxs
+    // -------------------------------------------------------------------------
+    /// Return the four center spaces, or an error if the board has an odd
+    /// number of rows or columns.
+    pub fn try_center_idxs(&self) -> Result<HashSet<usize>, Error> {
+        let center_idxs = self.try_center_mask()?.to_idxs();
+
+        Ok(center_idxs)
     }
 
     // -------------------------------------------------------------------------
     pub fn corner_idxs(&self) -> HashSet<usize> {
-        let corner_idxs = HashSet::from([
-            0,
-            self.cols - 1,
-            self.elems - self.cols,
-            self.elems - 1,
-        ]);
-
-        corner_idxs
+        self.corner_mask().to_idxs()
     }
 
     /// Count the number of `Space::Building(building_type)` in each row and
@@ -155,13 +312,11 @@ impl Board {
         building_type: BuildingType,
     ) -> (HashMap<usize, u32>, HashMap<usize, u32>) {
         let (count_per_row, count_per_col) =
-            self.spaces.iter().enumerate().fold(
+            self.building_mask(building_type).iter().fold(
                 (HashMap::new(), HashMap::new()),
-                |(mut count_per_row, mut count_per_col), (idx, space)| {
-                    if space.building_type_eq(building_type) {
-                        *count_per_row.entry(self.row(idx)).or_insert(0) += 1;
-                        *count_per_col.entry(self.col(idx)).or_insert(0) += 1;
-                    }
+                |(mut count_per_row, mut count_per_col), idx| {
+                    *count_per_row.entry(self.row(idx)).or_insert(0) += 1;
+                    *count_per_col.entry(self.col(idx)).or_insert(0) += 1;
                     (count_per_row, count_per_col)
                 },
             );
@@ -176,16 +331,7 @@ impl Board {
         &self,
         idxs: &HashSet<usize>,
     ) -> HashSet<BuildingType> {
-        let unique_building_types =
-            idxs.into_iter().fold(HashSet::new(), |mut s, idx| {
-                let space = &self.spaces[*idx];
-                if let Some(building_type) = space.building_type() {
-                    s.insert(building_type);
-                }
-                s
-            });
-
-        unique_building_types
+        self.unique_building_types_in_mask(&Bitmask::from(idxs))
     }
 
     // -------------------------------------------------------------------------
@@ -194,7 +340,7 @@ impl Board {
         idx: usize,
     ) -> HashSet<BuildingType> {
         let unique_adjacent_building_types =
-            self.unique_building_types_in_idx_set(&self.adjacent_idxs(idx));
+            self.unique_building_types_in_mask(self.adjacent_mask(idx));
 
         unique_adjacent_building_types
     }
@@ -205,88 +351,28 @@ impl Board {
         idx: usize,
     ) -> HashSet<BuildingType> {
         let unique_surrounding_building_types =
-            self.unique_building_types_in_idx_set(&self.surrounding_idxs(idx));
+            self.unique_building_types_in_mask(self.surrounding_mask(idx));
 
         unique_surrounding_building_types
     }
 
-    // -------------------------------------------------------------------------
-    fn contiguous_group(
-        &self,
-        building_types: &HashSet<BuildingType>,
-        visited: &mut Vec<bool>,
-        idx: usize,
-    ) -> HashSet<usize> {
-        let mut queue = vec![idx];
-        let mut group = HashSet::new();
-        visited[idx] = true;
-
-        // Breadth-first search to find one contiguous group of buildings of
-        // some type in building_types.
-        while !queue.is_empty() {
-            let cur = queue.pop().unwrap();
-            for adjacent_idx in self.adjacent_idxs(cur).into_iter() {
-                let space = &self.spaces()[adjacent_idx];
-                if let Some(building_type) = space.building_type() {
-                    if !visited[adjacent_idx]
-                        && building_types.contains(&building_type)
-                    {
-                        visited[adjacent_idx] = true;
-                        queue.push(adjacent_idx);
-                    }
-                }
-            }
-            group.insert(cur);
-        }
-
-        group
-    }
-
     // -------------------------------------------------------------------------
     pub fn contiguous_groups(
         &self,
         building_types: &HashSet<BuildingType>,
     ) -> Vec<HashSet<usize>> {
         let contiguous_groups = self
-            .spaces()
-            .iter()
-            .enumerate()
-            .fold(
-                (Vec::new(), vec![false; self.elems]),
-                |(mut groups, mut visited), (idx, space)| {
-                    if let Some(building_type) = space.building_type() {
-                        if building_types.contains(&building_type)
-                            && !visited[idx]
-                        {
-                            let group = self.contiguous_group(
-                                &building_types,
-                                &mut visited,
-                                idx,
-                            );
-                            groups.push(group);
-                        }
-                    }
-                    (groups, visited)
-                },
-            )
-            .0;
+            .contiguous_group_masks(&self.building_types_mask(building_types))
+            .into_iter()
+            .map(|group| group.to_idxs())
+            .collect();
 
         contiguous_groups
     }
 
     // -------------------------------------------------------------------------
     pub fn count_building_type(&self, building_type: BuildingType) -> u32 {
-        // TODO - replace with if-let chain with future Rust version.
-        let count = self.spaces.iter().fold(0, |mut n, space| {
-            if let Some(bt) = space.building_type() {
-                if bt == building_type {
-                    n += 1;
-                }
-            }
-            n
-        });
-
-        count
+        self.building_mask(building_type).len()
     }
 
     // -------------------------------------------------------------------------
@@ -297,15 +383,9 @@ impl Board {
         idx: usize,
         adjacent_types: &HashSet<BuildingType>,
     ) -> u32 {
-        let count = self.adjacent_idxs(idx).iter().fold(0, |mut n, idx| {
-            let space = &self.spaces()[*idx];
-            if let Some(building_type) = space.building_type() {
-                if adjacent_types.contains(&building_type) {
-                    n += 1;
-                }
-            }
-            n
-        });
+        let count = (self.adjacent_mask(idx)
+            & self.building_types_mask(adjacent_types))
+        .len();
 
         count
     }
@@ -315,12 +395,53 @@ impl Board {
     where
         T: Place,
     {
-        self.spaces[idx] = item.to_space();
+        self.try_place(idx, item).unwrap()
+    }
+
+    // -------------------------------------------------------------------------
+    /// Place `item` on space #`idx`, or return an error if the space is not on
+    /// the board.
+    pub fn try_place<T>(&mut self, idx: usize, item: T) -> Result<(), Error>
+    where
+        T: Place,
+    {
+        if idx >= self.elems {
+            return Err(Error::IdxOutOfRange(idx));
+        }
+        self.set_space(idx, item.to_space());
+        self.buildings_when_built.remove(&idx);
+
+        Ok(())
     }
 
     // -------------------------------------------------------------------------
     pub fn remove(&mut self, idx: usize) {
-        self.spaces[idx] = Space::Empty;
+        self.try_remove(idx).unwrap()
+    }
+
+    // -------------------------------------------------------------------------
+    /// Empty space #`idx`, or return an error if the space is not on the board.
+    pub fn try_remove(&mut self, idx: usize) -> Result<(), Error> {
+        if idx >= self.elems {
+            return Err(Error::IdxOutOfRange(idx));
+        }
+        self.set_space(idx, Space::Empty);
+        self.buildings_when_built.remove(&idx);
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Put `space` on space #idx, keeping the building masks in step. Every
+    /// change to `spaces` goes through here.
+    fn set_space(&mut self, idx: usize, space: Space) {
+        if let Some(building_type) = self.spaces[idx].building_type() {
+            self.building_masks[building_type as usize].remove(idx);
+        }
+        if let Some(building_type) = space.building_type() {
+            self.building_masks[building_type as usize].insert(idx);
+        }
+        self.spaces[idx] = space;
     }
 
     // -------------------------------------------------------------------------
@@ -378,11 +499,17 @@ mod tests {
     #[test]
     fn test_diagonal_idxs() {
         let board = Board::new(4, 8);
-        assert_eq!(board.diagonal_idxs(0), HashSet::from([9]));
-        assert_eq!(board.diagonal_idxs(1), HashSet::from([8, 10]));
-        assert_eq!(board.diagonal_idxs(8), HashSet::from([1, 17]));
-        assert_eq!(board.diagonal_idxs(15), HashSet::from([6, 22]));
-        assert_eq!(board.diagonal_idxs(17), HashSet::from([8, 10, 24, 26]));
+        let diagonal_idxs = |idx| {
+            board
+                .surrounding_mask(idx)
+                .difference(board.adjacent_mask(idx))
+                .to_idxs()
+        };
+        assert_eq!(diagonal_idxs(0), HashSet::from([9]));
+        assert_eq!(diagonal_idxs(1), HashSet::from([8, 10]));
+        assert_eq!(diagonal_idxs(8), HashSet::from([1, 17]));
+        assert_eq!(diagonal_idxs(15), HashSet::from([6, 22]));
+        assert_eq!(diagonal_idxs(17), HashSet::from([8, 10, 24, 26]));
     }
 
     // -------------------------------------------------------------------------
@@ -420,6 +547,49 @@ mod tests {
 
         let board = Board::new(6, 6);
         assert_eq!(board.center_idxs(), HashSet::from([14, 15, 20, 21]));
+
+        let board = Board::new(5, 6);
+        assert_eq!(
+            board.try_center_idxs(),
+            Err(Error::NoCenter { rows: 5, cols: 6 })
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_try_new() {
+        assert!(Board::try_new(3, 3).is_ok());
+        assert_eq!(
+            Board::try_new(2, 4).err(),
+            Some(Error::BoardTooSmall { rows: 2, cols: 4 })
+        );
+        assert_eq!(
+            Board::try_new(4, 0).err(),
+            Some(Error::BoardTooSmall { rows: 4, cols: 0 })
+        );
+        assert!(Board::try_new(9, 15).is_ok());
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_try_place() {
+        let mut board = Board::new(4, 4);
+        assert_eq!(board.try_place(15, BuildingType::Blue), Ok(()));
+        assert!(board.spaces()[15].building_type_eq(BuildingType::Blue));
+        assert_eq!(
+            board.try_place(16, BuildingType::Blue),
+            Err(Error::IdxOutOfRange(16))
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_try_remove() {
+        let mut board = Board::new(4, 4);
+        board.place(15, BuildingType::Blue);
+        assert_eq!(board.try_remove(15), Ok(()));
+        assert_eq!(board.spaces()[15], Space::Empty);
+        assert_eq!(board.try_remove(16), Err(Error::IdxOutOfRange(16)));
     }
 
     // -------------------------------------------------------------------------
@@ -617,17 +787,12 @@ mod tests {
             BuildingType::Red,
         ]);
 
-        let mut visited = vec![false; board.elems];
-        let group = board.contiguous_group(&building_types, &mut visited, 0);
-        assert_eq!(group, HashSet::from([0, 1]));
-
-        let mut visited = vec![false; board.elems];
-        let group = board.contiguous_group(&building_types, &mut visited, 3);
-        assert_eq!(group, HashSet::from([3]));
+        let mask = board.building_types_mask(&building_types);
+        let group = board.contiguous_group(&mask, 0);
+        assert_eq!(group.to_idxs(), HashSet::from([0, 1]));
 
-        let mut visited = vec![false; board.elems];
-        let group = board.contiguous_group(&building_types, &mut visited, 3);
-        assert_eq!(group, HashSet::from([3]));
+        let group = board.contiguous_group(&mask, 3);
+        assert_eq!(group.to_idxs(), HashSet::from([3]));
 
         board.place(4, BuildingType::Green);
         let building_types = HashSet::from([
@@ -636,9 +801,9 @@ mod tests {
             BuildingType::Red,
             BuildingType::Orange,
         ]);
-        let mut visited = vec![false; board.elems];
-        let group = board.contiguous_group(&building_types, &mut visited, 3);
-        assert_eq!(group, HashSet::from([0, 1, 2, 3, 4]));
+        let mask = board.building_types_mask(&building_types);
+        let group = board.contiguous_group(&mask, 3);
+        assert_eq!(group.to_idxs(), HashSet::from([0, 1, 2, 3, 4]));
     }
 
     // -------------------------------------------------------------------------
@@ -657,17 +822,17 @@ mod tests {
 
         let building_types = HashSet::from([BuildingType::Blue]);
         let groups = board.contiguous_groups(&building_types);
-        let ans = vec![HashSet::from([0, 1, 4]), HashSet::from([3])];
-        let eq = groups.iter().all(|s| ans.contains(&s))
-            && ans.iter().all(|s| groups.contains(&s));
+        let ans = [HashSet::from([0, 1, 4]), HashSet::from([3])];
+        let eq = groups.iter().all(|s| ans.contains(s))
+            && ans.iter().all(|s| groups.contains(s));
         assert!(eq);
 
         let building_types =
             HashSet::from([BuildingType::Blue, BuildingType::Magenta]);
         let groups = board.contiguous_groups(&building_types);
-        let ans = vec![HashSet::from([0, 1, 2, 3, 4])];
-        let eq = groups.iter().all(|s| ans.contains(&s))
-            && ans.iter().all(|s| groups.contains(&s));
+        let ans = [HashSet::from([0, 1, 2, 3, 4])];
+        let eq = groups.iter().all(|s| ans.contains(s))
+            && ans.iter().all(|s| groups.contains(s));
         assert!(eq);
     }
 
@@ -694,4 +859,71 @@ mod tests {
         assert_eq!(count_per_row, HashMap::from([(1, 1)]));
         assert_eq!(count_per_col, HashMap::from([(1, 1)]));
     }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_building_mask() {
+        let mut board = Board::new(4, 4);
+        board.place(0, BuildingType::Blue);
+        board.place(5, BuildingType::Blue);
+        board.place(6, BuildingType::Red);
+        assert_eq!(
+            board.building_mask(BuildingType::Blue),
+            &Bitmask::from_iter([0, 5])
+        );
+        assert_eq!(board.buildings_mask(), Bitmask::from_iter([0, 5, 6]));
+
+        // Replacing or removing a building moves it out of its type's mask.
+        board.place(5, BuildingType::Red);
+        board.remove(0);
+        assert!(board.building_mask(BuildingType::Blue).is_empty());
+        assert_eq!(
+            board.building_types_mask(&HashSet::from([
+                BuildingType::Blue,
+                BuildingType::Red
+            ])),
+            Bitmask::from_iter([5, 6])
+        );
+
+        // The masks are rebuilt when a board is read back in.
+        let json = serde_json::to_string(&board).unwrap();
+        let board: Board = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            board.building_mask(BuildingType::Red),
+            &Bitmask::from_iter([5, 6])
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_contiguous_group_masks() {
+        let mut board = Board::new(3, 4);
+        for idx in [0, 1, 5, 3, 7, 11] {
+            board.place(idx, BuildingType::Green);
+        }
+        let groups = board
+            .contiguous_group_masks(board.building_mask(BuildingType::Green));
+        assert_eq!(
+            groups,
+            vec![
+                Bitmask::from_iter([0, 1, 5]),
+                Bitmask::from_iter([3, 7, 11])
+            ]
+        );
+
+        // Groups on a board too large for one word of a mask.
+        let mut board = Board::new(12, 12);
+        for idx in [52, 64, 76, 143, 142] {
+            board.place(idx, BuildingType::Green);
+        }
+        let groups = board
+            .contiguous_group_masks(board.building_mask(BuildingType::Green));
+        assert_eq!(
+            groups,
+            vec![
+                Bitmask::from_iter([52, 64, 76]),
+                Bitmask::from_iter([142, 143])
+            ]
+        );
+    }
 }
@@ -0,0 +1,485 @@
+use std::error;
+use std::fmt;
+use std::iter::Peekable;
+
+use crate::board::space::{BuildingType, Resource, Space};
+use crate::board::Board;
+use crate::error::Error;
+
+// A board is written one row per line, with one symbol per space. Spaces
+// between symbols are optional, and blank lines and lines starting with `#`
+// are ignored.
+//
+//   .  an empty space
+//   b  brick     g  glass     s  stone     h  wheat     w  wood
+//   K  black     B  blue      A  gray      G  green
+//   M  magenta   O  orange    R  red       Y  yellow
+//
+// A building may be followed by the resources on it:
+//
+//   K[bg]  a building storing brick and glass, as a Warehouse does
+//   K(b)   a building assigned brick, as a Factory or Bank is; `K()` has yet
+//          to be assigned a resource
+//   K<b>   a building holding a single brick
+//
+// and then by the number of buildings in the town when it was constructed,
+// which the Shrine of the Elder Tree scores by:
+//
+//   M{5}   a monument built as the town's fifth building
+//
+// For example, a 4x4 town with a Warehouse in the corner:
+//
+//   B R . w
+//   O A G .
+//   Y Y . .
+//   M . . K[bw]
+
+// =============================================================================
+/// Reasons a board cannot be read from its notation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum NotationError {
+    /// The notation has no rows.
+    Empty,
+    /// A row has a different number of spaces than the first row.
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A symbol is not a building, a resource, or an empty space.
+    InvalidSymbol { row: usize, symbol: char },
+    /// A list of resources after a building is never closed.
+    UnclosedResources { row: usize },
+    /// The number of buildings after a building is never closed or is not a
+    /// number.
+    InvalidBuildingsWhenBuilt { row: usize },
+    /// The rows and columns do not make a valid board.
+    InvalidSize { rows: usize, cols: usize },
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::Empty => write!(f, "the board has no rows"),
+            NotationError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} has {found} spaces, but the first row has {expected}"
+            ),
+            NotationError::InvalidSymbol { row, symbol } => {
+                write!(f, "'{symbol}' in row {row} is not a space")
+            }
+            NotationError::UnclosedResources { row } => {
+                write!(f, "a list of resources in row {row} is never closed")
+            }
+            NotationError::InvalidBuildingsWhenBuilt { row } => {
+                write!(f, "a number of buildings in row {row} is not valid")
+            }
+            NotationError::InvalidSize { rows, cols } => {
+                write!(f, "a {rows}x{cols} board is not allowed")
+            }
+        }
+    }
+}
+
+impl error::Error for NotationError {}
+
+impl From<NotationError> for Error {
+    fn from(err: NotationError) -> Self {
+        Error::Notation(err)
+    }
+}
+
+// -----------------------------------------------------------------------------
+pub fn building_type_from_symbol(symbol: char) -> Option<BuildingType> {
+    let building_type = match symbol {
+        'K' => BuildingType::Black,
+        'B' => BuildingType::Blue,
+        'A' => BuildingType::Gray,
+        'G' => BuildingType::Green,
+        'M' => BuildingType::Magenta,
+        'O' => BuildingType::Orange,
+        'R' => BuildingType::Red,
+        'Y' => BuildingType::Yellow,
+        _ => return None,
+    };
+
+    Some(building_type)
+}
+
+// -----------------------------------------------------------------------------
+fn building_type_to_symbol(building_type: BuildingType) -> char {
+    match building_type {
+        BuildingType::Black => 'K',
+        BuildingType::Blue => 'B',
+        BuildingType::Gray => 'A',
+        BuildingType::Green => 'G',
+        BuildingType::Magenta => 'M',
+        BuildingType::Orange => 'O',
+        BuildingType::Red => 'R',
+        BuildingType::Yellow => 'Y',
+    }
+}
+
+// -----------------------------------------------------------------------------
+pub fn resource_from_symbol(symbol: char) -> Option<Resource> {
+    let resource = match symbol {
+        'b' => Resource::Brick,
+        'g' => Resource::Glass,
+        's' => Resource::Stone,
+        'h' => Resource::Wheat,
+        'w' => Resource::Wood,
+        _ => return None,
+    };
+
+    Some(resource)
+}
+
+// -----------------------------------------------------------------------------
+fn resource_to_symbol(resource: Resource) -> char {
+    match resource {
+        Resource::Brick => 'b',
+        Resource::Glass => 'g',
+        Resource::Stone => 's',
+        Resource::Wheat => 'h',
+        Resource::Wood => 'w',
+    }
+}
+
+// -----------------------------------------------------------------------------
+/// Read the `{n}` that may follow a building, if there is one.
+fn parse_buildings_when_built(
+    row: usize,
+    symbols: &mut Peekable<impl Iterator<Item = char>>,
+) -> Result<Option<u32>, NotationError> {
+    if symbols.next_if_eq(&'{').is_none() {
+        return Ok(None);
+    }
+    let mut digits = String::new();
+    loop {
+        match symbols.next() {
+            Some('}') => break,
+            Some(c) => digits.push(c),
+            None => {
+                return Err(NotationError::InvalidBuildingsWhenBuilt { row })
+            }
+        }
+    }
+    let num_buildings = digits
+        .parse()
+        .map_err(|_| NotationError::InvalidBuildingsWhenBuilt { row })?;
+
+    Ok(Some(num_buildings))
+}
+
+// -----------------------------------------------------------------------------
+/// Read the spaces in one row of the notation, each with the number of
+/// buildings in the town when it was constructed, if given.
+fn parse_row(
+    row: usize,
+    line: &str,
+) -> Result<Vec<(Space, Option<u32>)>, NotationError> {
+    let mut spaces = Vec::new();
+    let mut symbols = line.chars().filter(|c| !c.is_whitespace()).peekable();
+    while let Some(symbol) = symbols.next() {
+        if symbol == '.' {
+            spaces.push((Space::Empty, None));
+        } else if let Some(resource) = resource_from_symbol(symbol) {
+            spaces.push((Space::Resource(resource), None));
+        } else if let Some(building_type) = building_type_from_symbol(symbol) {
+            let close = match symbols.peek() {
+                Some('[') => ']',
+                Some('(') => ')',
+                Some('<') => '>',
+                _ => {
+                    let buildings_when_built =
+                        parse_buildings_when_built(row, &mut symbols)?;
+                    spaces.push((
+                        Space::Building(building_type),
+                        buildings_when_built,
+                    ));
+                    continue;
+                }
+            };
+            let open = symbols.next().unwrap();
+
+            let mut resources = Vec::new();
+            loop {
+                match symbols.next() {
+                    Some(c) if c == close => break,
+                    Some(c) => {
+                        let resource = resource_from_symbol(c).ok_or(
+                            NotationError::InvalidSymbol { row, symbol: c },
+                        )?;
+                        resources.push(resource);
+                    }
+                    None => {
+                        return Err(NotationError::UnclosedResources { row })
+                    }
+                }
+            }
+
+            let space = match (open, resources.as_slice()) {
+                ('[', _) => {
+                    Space::BuildingWithResources(building_type, resources)
+                }
+                ('(', []) => {
+                    Space::BuildingWithOptResource(building_type, None)
+                }
+                ('(', [resource]) => Space::BuildingWithOptResource(
+                    building_type,
+                    Some(*resource),
+                ),
+                ('<', [resource]) => {
+                    Space::BuildingWithResource(building_type, *resource)
+                }
+                _ => {
+                    return Err(NotationError::InvalidSymbol {
+                        row,
+                        symbol: close,
+                    })
+                }
+            };
+            let buildings_when_built =
+                parse_buildings_when_built(row, &mut symbols)?;
+            spaces.push((space, buildings_when_built));
+        } else {
+            return Err(NotationError::InvalidSymbol { row, symbol });
+        }
+    }
+
+    Ok(spaces)
+}
+
+// =============================================================================
+impl Space {
+    /// Return the space in board notation.
+    pub fn to_notation(&self) -> String {
+        let resources_to_notation = |resources: &[Resource]| -> String {
+            resources.iter().map(|r| resource_to_symbol(*r)).collect()
+        };
+        let notation = match self {
+            Space::Building(building_type) => {
+                building_type_to_symbol(*building_type).to_string()
+            }
+            Space::BuildingWithOptResource(building_type, resource_opt) => {
+                format!(
+                    "{}({})",
+                    building_type_to_symbol(*building_type),
+                    resources_to_notation(resource_opt.as_slice())
+                )
+            }
+            Space::BuildingWithResource(building_type, resource) => format!(
+                "{}<{}>",
+                building_type_to_symbol(*building_type),
+                resource_to_symbol(*resource)
+            ),
+            Space::BuildingWithResources(building_type, resources) => format!(
+                "{}[{}]",
+                building_type_to_symbol(*building_type),
+                resources_to_notation(resources)
+            ),
+            Space::Resource(resource) => {
+                resource_to_symbol(*resource).to_string()
+            }
+            Space::Empty => String::from("."),
+        };
+
+        notation
+    }
+}
+
+// =============================================================================
+impl Board {
+    /// Read a board written in board notation.
+    pub fn from_notation(notation: &str) -> Result<Self, NotationError> {
+        let rows = notation
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .enumerate()
+            .map(|(row, line)| parse_row(row, line))
+            .collect::<Result<Vec<_>, NotationError>>()?;
+
+        let cols = rows.first().ok_or(NotationError::Empty)?.len();
+        if let Some((row, spaces)) = rows
+            .iter()
+            .enumerate()
+            .find(|(_, spaces)| spaces.len() != cols)
+        {
+            return Err(NotationError::RaggedRow {
+                row,
+                expected: cols,
+                found: spaces.len(),
+            });
+        }
+
+        let mut board = Board::try_new(rows.len(), cols).map_err(|_| {
+            NotationError::InvalidSize {
+                rows: rows.len(),
+                cols,
+            }
+        })?;
+        for (idx, (space, buildings_when_built)) in
+            rows.into_iter().flatten().enumerate()
+        {
+            board.place(idx, space);
+            if let Some(num_buildings) = buildings_when_built {
+                board.set_buildings_when_built(idx, num_buildings);
+            }
+        }
+
+        Ok(board)
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the board in board notation, one line per row with the spaces
+    /// separated by a single space.
+    pub fn to_notation(&self) -> String {
+        let space_to_notation = |idx: usize| -> String {
+            let notation = self.spaces[idx].to_notation();
+            match self.buildings_when_built(idx) {
+                Some(num_buildings) => format!("{notation}{{{num_buildings}}}"),
+                None => notation,
+            }
+        };
+        let notation = (0..self.rows)
+            .map(|row| {
+                (0..self.cols)
+                    .map(|col| space_to_notation(self.idx(row, col)))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        notation
+    }
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_from_notation() {
+        let board = Board::from_notation(
+            "
+            # A town with a Warehouse in the corner.
+            B R . w
+            O A G .
+            Y Y . .
+            M . . K[bw]
+            ",
+        )
+        .unwrap();
+        assert_eq!(board.rows(), 4);
+        assert_eq!(board.cols(), 4);
+        assert_eq!(board.spaces()[0], Space::Building(BuildingType::Blue));
+        assert_eq!(board.spaces()[2], Space::Empty);
+        assert_eq!(board.spaces()[3], Space::Resource(Resource::Wood));
+        assert_eq!(board.spaces()[5], Space::Building(BuildingType::Gray));
+        assert_eq!(
+            board.spaces()[15],
+            Space::BuildingWithResources(
+                BuildingType::Black,
+                vec![Resource::Brick, Resource::Wood]
+            )
+        );
+
+        // Spaces between symbols are optional.
+        let board = Board::from_notation("K()K(h)K<s>\nbgs\nhw.").unwrap();
+        assert_eq!(
+            board.spaces()[..3],
+            [
+                Space::BuildingWithOptResource(BuildingType::Black, None),
+                Space::BuildingWithOptResource(
+                    BuildingType::Black,
+                    Some(Resource::Wheat)
+                ),
+                Space::BuildingWithResource(
+                    BuildingType::Black,
+                    Resource::Stone
+                ),
+            ]
+        );
+        assert_eq!(board.spaces()[6], Space::Resource(Resource::Wheat));
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_from_notation_errors() {
+        assert_eq!(
+            Board::from_notation("\n# nothing\n").err(),
+            Some(NotationError::Empty)
+        );
+        assert_eq!(
+            Board::from_notation("...\n....\n...").err(),
+            Some(NotationError::RaggedRow {
+                row: 1,
+                expected: 3,
+                found: 4
+            })
+        );
+        assert_eq!(
+            Board::from_notation("...\n.X.\n...").err(),
+            Some(NotationError::InvalidSymbol {
+                row: 1,
+                symbol: 'X'
+            })
+        );
+        assert_eq!(
+            Board::from_notation("..K[b\n...\n...").err(),
+            Some(NotationError::UnclosedResources { row: 0 })
+        );
+        assert_eq!(
+            Board::from_notation("K(bg)..\n...\n...").err(),
+            Some(NotationError::InvalidSymbol {
+                row: 0,
+                symbol: ')'
+            })
+        );
+        assert_eq!(
+            Board::from_notation("...\n...").err(),
+            Some(NotationError::InvalidSize { rows: 2, cols: 3 })
+        );
+        for notation in
+            ["M{5..\n...\n...", "M{}..\n...\n...", "M{x}..\n...\n..."]
+        {
+            assert_eq!(
+                Board::from_notation(notation).err(),
+                Some(NotationError::InvalidBuildingsWhenBuilt { row: 0 })
+            );
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_to_notation() {
+        let mut board = Board::new(3, 4);
+        board.place(0, BuildingType::Magenta);
+        board.place(1, Resource::Glass);
+        board.place(5, (BuildingType::Black, vec![Resource::Stone], 3));
+        board.place(6, (BuildingType::Black, None));
+        board.place(7, (BuildingType::Black, Resource::Brick));
+        board.place(11, BuildingType::Yellow);
+        board.record_buildings_when_built(11);
+        let notation = board.to_notation();
+        assert_eq!(notation, "M g . .\n. K[s] K() K<b>\n. . . Y{5}");
+
+        let parsed = Board::from_notation(&notation).unwrap();
+        assert_eq!(parsed.spaces(), board.spaces());
+        assert_eq!(parsed.buildings_when_built(11), Some(5));
+        assert_eq!(parsed.buildings_when_built(0), None);
+        assert_eq!(parsed.to_notation(), notation);
+
+        let parsed = Board::from_notation("K<b>{3} M{12} .\n...\n...").unwrap();
+        assert_eq!(parsed.buildings_when_built(0), Some(3));
+        assert_eq!(parsed.buildings_when_built(1), Some(12));
+    }
+}
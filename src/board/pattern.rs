@@ -0,0 +1,591 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::board::space::{BuildingType, Resource, Space};
+use crate::board::Board;
+use crate::building_config::{
+    BlackBuilding, BlueBuilding, BuildingConfig, GrayBuilding, GreenBuilding,
+    MagentaBuilding, OrangeBuilding, RedBuilding, YellowBuilding,
+};
+
+// Shorthand for the cells of the recipes below.
+const __: Option<Resource> = None;
+const BR: Option<Resource> = Some(Resource::Brick);
+const GL: Option<Resource> = Some(Resource::Glass);
+const ST: Option<Resource> = Some(Resource::Stone);
+const WH: Option<Resource> = Some(Resource::Wheat);
+const WO: Option<Resource> = Some(Resource::Wood);
+
+// =============================================================================
+/// A rectangular arrangement of resources that constructs a building. Cells
+/// that are `None` are not part of the pattern.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pattern {
+    rows: usize,
+    cols: usize,
+    cells: Vec<Option<Resource>>,
+}
+
+impl Pattern {
+    pub fn new(grid: Vec<Vec<Option<Resource>>>) -> Self {
+        let rows = grid.len();
+        let cols = grid.first().map(|row| row.len()).unwrap_or(0);
+        assert!(rows > 0 && cols > 0);
+        assert!(grid.iter().all(|row| row.len() == cols));
+        let cells = grid.into_iter().flatten().collect();
+        Self { rows, cols, cells }
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn cell(&self, row: usize, col: usize) -> Option<Resource> {
+        self.cells[row * self.cols + col]
+    }
+
+    // -------------------------------------------------------------------------
+    /// Rotate the pattern 90 degrees clockwise.
+    fn rotate(&self) -> Self {
+        let (rows, cols) = (self.cols, self.rows);
+        let cells = (0..rows * cols)
+            .map(|idx| {
+                let (row, col) = (idx / cols, idx % cols);
+                self.cell(self.rows - 1 - col, row)
+            })
+            .collect();
+
+        Self { rows, cols, cells }
+    }
+
+    // -------------------------------------------------------------------------
+    /// Mirror the pattern across its vertical axis.
+    fn reflect(&self) -> Self {
+        let cells = (0..self.rows * self.cols)
+            .map(|idx| {
+                let (row, col) = (idx / self.cols, idx % self.cols);
+                self.cell(row, self.cols - 1 - col)
+            })
+            .collect();
+
+        Self {
+            rows: self.rows,
+            cols: self.cols,
+            cells,
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return every distinct rotation and reflection of the pattern.
+    pub fn orientations(&self) -> Vec<Pattern> {
+        let orientations = [self.clone(), self.reflect()].into_iter().fold(
+            Vec::new(),
+            |mut orientations, mut pattern| {
+                for _ in 0..4 {
+                    if !orientations.contains(&pattern) {
+                        orientations.push(pattern.clone());
+                    }
+                    pattern = pattern.rotate();
+                }
+                orientations
+            },
+        );
+
+        orientations
+    }
+}
+
+// =============================================================================
+impl BlackBuilding {
+    pub fn pattern(&self) -> Pattern {
+        let grid = match self {
+            BlackBuilding::Bank => vec![vec![WH, WH, __], vec![WO, GL, BR]],
+            BlackBuilding::Factory => {
+                vec![vec![WO, __, __, __], vec![BR, ST, ST, BR]]
+            }
+            BlackBuilding::TradingPost => {
+                vec![vec![ST, WO, __], vec![ST, WO, BR]]
+            }
+            BlackBuilding::Warehouse => {
+                vec![vec![WH, WO, WH], vec![BR, __, BR]]
+            }
+        };
+
+        Pattern::new(grid)
+    }
+}
+
+impl BlueBuilding {
+    pub fn pattern(&self) -> Pattern {
+        let grid = match self {
+            BlueBuilding::Cottage => vec![vec![__, WH], vec![BR, GL]],
+        };
+
+        Pattern::new(grid)
+    }
+}
+
+impl GrayBuilding {
+    pub fn pattern(&self) -> Pattern {
+        let grid = match self {
+            GrayBuilding::Fountain
+            | GrayBuilding::Millstone
+            | GrayBuilding::Shed
+            | GrayBuilding::Well => vec![vec![WO, ST]],
+        };
+
+        Pattern::new(grid)
+    }
+}
+
+impl GreenBuilding {
+    pub fn pattern(&self) -> Pattern {
+        let grid = match self {
+            GreenBuilding::Almshouse => vec![vec![ST, ST, GL]],
+            GreenBuilding::FeastHall => vec![vec![WO, WO, GL]],
+            GreenBuilding::Inn => vec![vec![WH, ST, GL]],
+            GreenBuilding::Tavern => vec![vec![BR, BR, GL]],
+        };
+
+        Pattern::new(grid)
+    }
+}
+
+impl MagentaBuilding {
+    pub fn pattern(&self) -> Pattern {
+        let grid = match self {
+            MagentaBuilding::ArchitectsGuild => {
+                vec![vec![__, __, GL], vec![WO, BR, ST]]
+            }
+            MagentaBuilding::ArchiveOfTheSecondAge => {
+                vec![vec![WH, WH], vec![BR, GL]]
+            }
+            MagentaBuilding::BarrettCastle => {
+                vec![vec![WH, __, __, WO], vec![WO, GL, GL, BR]]
+            }
+            MagentaBuilding::CathedralOfCaterina => {
+                vec![vec![__, WH], vec![ST, GL]]
+            }
+            MagentaBuilding::FortIronweed => {
+                vec![vec![WH, __, BR], vec![ST, WO, ST]]
+            }
+            MagentaBuilding::GrandMausoleumOfTheRodina => {
+                vec![vec![WH, __, __], vec![BR, ST, ST]]
+            }
+            MagentaBuilding::GroveUniversity => {
+                vec![vec![__, BR, __], vec![ST, GL, ST]]
+            }
+            MagentaBuilding::MandrasPalace => {
+                vec![vec![WH, GL], vec![BR, WO]]
+            }
+            MagentaBuilding::ObeliskOfTheCrescent => {
+                vec![vec![WH, __, __], vec![BR, GL, BR]]
+            }
+            MagentaBuilding::OpaleyesWatch => {
+                vec![vec![WO, __, __], vec![BR, GL, GL]]
+            }
+            MagentaBuilding::ShrineOfTheElderTree => {
+                vec![vec![BR, WH, ST], vec![WO, GL, WO]]
+            }
+            MagentaBuilding::SilvaForum => {
+                vec![vec![BR, __, __], vec![WO, WH, WO]]
+            }
+            MagentaBuilding::StatueOfTheBondmaker => {
+                vec![vec![WH, __, __], vec![ST, GL, ST]]
+            }
+            MagentaBuilding::TheSkyBaths => {
+                vec![vec![__, BR, __], vec![ST, WH, GL]]
+            }
+            MagentaBuilding::TheStarloom => {
+                vec![vec![__, __, WH], vec![WO, GL, GL]]
+            }
+        };
+
+        Pattern::new(grid)
+    }
+}
+
+impl OrangeBuilding {
+    pub fn pattern(&self) -> Pattern {
+        let grid = match self {
+            OrangeBuilding::Abbey => vec![vec![__, __, GL], vec![BR, ST, ST]],
+            OrangeBuilding::Chapel => vec![vec![__, __, GL], vec![ST, GL, ST]],
+            OrangeBuilding::Cloister => {
+                vec![vec![__, __, GL], vec![WO, BR, ST]]
+            }
+            OrangeBuilding::Temple => vec![vec![__, __, GL], vec![BR, BR, ST]],
+        };
+
+        Pattern::new(grid)
+    }
+}
+
+impl RedBuilding {
+    pub fn pattern(&self) -> Pattern {
+        let grid = match self {
+            RedBuilding::Farm => vec![vec![WH, WH], vec![WO, WO]],
+            RedBuilding::Granary => vec![vec![WH, WH], vec![WO, BR]],
+            RedBuilding::Greenhouse => vec![vec![WH, GL], vec![WO, WO]],
+            RedBuilding::Orchard => vec![vec![ST, WH], vec![WH, WO]],
+        };
+
+        Pattern::new(grid)
+    }
+}
+
+impl YellowBuilding {
+    pub fn pattern(&self) -> Pattern {
+        let grid = match self {
+            YellowBuilding::Bakery => vec![vec![__, WH, __], vec![BR, GL, BR]],
+            YellowBuilding::Market => vec![vec![__, ST, __], vec![WO, GL, WO]],
+            YellowBuilding::Tailor => vec![vec![__, WH, __], vec![ST, GL, ST]],
+            YellowBuilding::Theater => {
+                vec![vec![__, ST, __], vec![WO, GL, WO]]
+            }
+        };
+
+        Pattern::new(grid)
+    }
+}
+
+impl BuildingConfig {
+    /// Return the pattern of each building in the configuration, keyed by the
+    /// type of building it constructs.
+    pub fn patterns(&self) -> HashMap<BuildingType, Pattern> {
+        let patterns = HashMap::from([
+            (BuildingType::Black, self.black().pattern()),
+            (BuildingType::Blue, self.blue().pattern()),
+            (BuildingType::Gray, self.gray().pattern()),
+            (BuildingType::Green, self.green().pattern()),
+            (BuildingType::Magenta, self.magenta().pattern()),
+            (BuildingType::Orange, self.orange().pattern()),
+            (BuildingType::Red, self.red().pattern()),
+            (BuildingType::Yellow, self.yellow().pattern()),
+        ]);
+
+        patterns
+    }
+}
+
+// =============================================================================
+/// A placement of a building's pattern on a board: the type of building that
+/// can be constructed, and the indices of the resources that form it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatternMatch {
+    building_type: BuildingType,
+    idxs: HashSet<usize>,
+}
+
+impl PatternMatch {
+    pub fn new(building_type: BuildingType, idxs: HashSet<usize>) -> Self {
+        Self {
+            building_type,
+            idxs,
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn building_type(&self) -> BuildingType {
+        self.building_type
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn idxs(&self) -> &HashSet<usize> {
+        &self.idxs
+    }
+}
+
+// =============================================================================
+impl Board {
+    /// Return the indices covered by `pattern` (in the orientation given) if
+    /// its top left corner is placed on space #idx and every resource matches.
+    /// The spaces in `wild_idxs` match any resource, but at least one real
+    /// resource is needed.
+    fn match_pattern_at(
+        &self,
+        pattern: &Pattern,
+        idx: usize,
+        wild_idxs: &HashSet<usize>,
+    ) -> Option<HashSet<usize>> {
+        let (row, col) = (self.row(idx), self.col(idx));
+        if row + pattern.rows() > self.rows()
+            || col + pattern.cols() > self.cols()
+        {
+            return None;
+        }
+
+        let mut idxs = HashSet::new();
+        for pattern_row in 0..pattern.rows() {
+            for pattern_col in 0..pattern.cols() {
+                if let Some(resource) = pattern.cell(pattern_row, pattern_col) {
+                    let board_idx =
+                        self.idx(row + pattern_row, col + pattern_col);
+                    if self.spaces()[board_idx] != Space::Resource(resource)
+                        && !wild_idxs.contains(&board_idx)
+                    {
+                        return None;
+                    }
+                    idxs.insert(board_idx);
+                }
+            }
+        }
+        if idxs.is_subset(wild_idxs) {
+            return None;
+        }
+
+        Some(idxs)
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return every set of indices where some rotation or reflection of
+    /// `pattern` is currently formed by resources on the board.
+    pub fn find_pattern(&self, pattern: &Pattern) -> Vec<HashSet<usize>> {
+        self.find_pattern_with_wilds(pattern, &HashSet::new())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Like `find_pattern`, but the spaces in `wild_idxs` stand in for any
+    /// resource.
+    pub fn find_pattern_with_wilds(
+        &self,
+        pattern: &Pattern,
+        wild_idxs: &HashSet<usize>,
+    ) -> Vec<HashSet<usize>> {
+        let matches = pattern.orientations().iter().fold(
+            Vec::new(),
+            |mut matches, orientation| {
+                for idx in 0..self.elems() {
+                    if let Some(idxs) =
+                        self.match_pattern_at(orientation, idx, wild_idxs)
+                    {
+                        if !matches.contains(&idxs) {
+                            matches.push(idxs);
+                        }
+                    }
+                }
+                matches
+            },
+        );
+
+        matches
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the spaces that may be used as any resource when constructing:
+    /// the Trading Posts, if they are in play.
+    pub fn wild_idxs(
+        &self,
+        building_config: &BuildingConfig,
+    ) -> HashSet<usize> {
+        let wild_idxs = if building_config.black() == BlackBuilding::TradingPost
+        {
+            (0..self.elems())
+                .filter(|idx| {
+                    self.spaces()[*idx].building_type_eq(BuildingType::Black)
+                })
+                .collect()
+        } else {
+            HashSet::new()
+        };
+
+        wild_idxs
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return every placement on the board where the pattern of a building in
+    /// `building_config` is currently matched. Once the town has its monument,
+    /// the monument's pattern is no longer of any use.
+    pub fn matching_patterns(
+        &self,
+        building_config: &BuildingConfig,
+    ) -> Vec<PatternMatch> {
+        let wild_idxs = self.wild_idxs(building_config);
+        let has_monument = self.count_building_type(BuildingType::Magenta) > 0;
+        let mut patterns: Vec<(BuildingType, Pattern)> = building_config
+            .patterns()
+            .into_iter()
+            .filter(|(building_type, _)| {
+                !(has_monument && *building_type == BuildingType::Magenta)
+            })
+            .collect();
+        patterns.sort_by_key(|(building_type, _)| *building_type as usize);
+
+        let matches = patterns.into_iter().fold(
+            Vec::new(),
+            |mut matches, (building_type, pattern)| {
+                for idxs in self.find_pattern_with_wilds(&pattern, &wild_idxs) {
+                    matches.push(PatternMatch::new(building_type, idxs));
+                }
+                matches
+            },
+        );
+
+        matches
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return `true` if the town is complete: there is no empty space left to
+    /// place a resource on, and no pattern left to construct.
+    pub fn is_complete(&self, building_config: &BuildingConfig) -> bool {
+        let is_complete = !self.spaces().contains(&Space::Empty)
+            && self.matching_patterns(building_config).is_empty();
+
+        is_complete
+    }
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::vec_hashset_eq;
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_rotate() {
+        let pattern = Pattern::new(vec![vec![__, WH], vec![BR, GL]]);
+        let rotated = Pattern::new(vec![vec![BR, __], vec![GL, WH]]);
+        assert_eq!(pattern.rotate(), rotated);
+
+        let pattern = Pattern::new(vec![vec![WO, ST]]);
+        let rotated = Pattern::new(vec![vec![WO], vec![ST]]);
+        assert_eq!(pattern.rotate(), rotated);
+        assert_eq!(pattern.rotate().rotate().rotate().rotate(), pattern);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_reflect() {
+        let pattern = Pattern::new(vec![vec![__, __, GL], vec![BR, ST, ST]]);
+        let reflected = Pattern::new(vec![vec![GL, __, __], vec![ST, ST, BR]]);
+        assert_eq!(pattern.reflect(), reflected);
+        assert_eq!(pattern.reflect().reflect(), pattern);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_orientations() {
+        // Asymmetric patterns have eight orientations.
+        assert_eq!(BlueBuilding::Cottage.pattern().orientations().len(), 8);
+        assert_eq!(OrangeBuilding::Temple.pattern().orientations().len(), 8);
+
+        // Symmetric patterns have fewer.
+        assert_eq!(RedBuilding::Farm.pattern().orientations().len(), 4);
+        assert_eq!(GrayBuilding::Well.pattern().orientations().len(), 4);
+        assert_eq!(YellowBuilding::Tailor.pattern().orientations().len(), 4);
+        let pattern = Pattern::new(vec![vec![ST, ST], vec![ST, ST]]);
+        assert_eq!(pattern.orientations(), vec![pattern]);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_find_pattern() {
+        let mut board = Board::new(4, 4);
+        let pattern = BlueBuilding::Cottage.pattern();
+        assert!(board.find_pattern(&pattern).is_empty());
+
+        board.place(1, Resource::Wheat);
+        board.place(4, Resource::Brick);
+        board.place(5, Resource::Glass);
+        assert_eq!(
+            board.find_pattern(&pattern),
+            vec![HashSet::from([1, 4, 5])]
+        );
+
+        // Reflected across the glass.
+        board.place(6, Resource::Brick);
+        let ans = vec![HashSet::from([1, 4, 5]), HashSet::from([1, 5, 6])];
+        assert!(vec_hashset_eq(&board.find_pattern(&pattern), &ans));
+
+        // A building in the way breaks the pattern.
+        board.place(1, BuildingType::Red);
+        assert!(board.find_pattern(&pattern).is_empty());
+
+        // Patterns may not wrap around the edge of the board.
+        let mut board = Board::new(4, 4);
+        board.place(3, Resource::Wood);
+        board.place(4, Resource::Stone);
+        let pattern = GrayBuilding::Well.pattern();
+        assert!(board.find_pattern(&pattern).is_empty());
+
+        board.place(7, Resource::Stone);
+        assert_eq!(board.find_pattern(&pattern), vec![HashSet::from([3, 7])]);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_find_pattern_with_wilds() {
+        let mut board = Board::new(4, 4);
+        let pattern = GrayBuilding::Well.pattern();
+        board.place(0, BuildingType::Black);
+        board.place(5, BuildingType::Black);
+        let wild_idxs = HashSet::from([0, 5]);
+
+        // Wild spaces alone do not form a pattern.
+        assert!(board
+            .find_pattern_with_wilds(&pattern, &wild_idxs)
+            .is_empty());
+
+        board.place(1, Resource::Stone);
+        let ans = vec![HashSet::from([0, 1]), HashSet::from([1, 5])];
+        assert!(vec_hashset_eq(
+            &board.find_pattern_with_wilds(&pattern, &wild_idxs),
+            &ans
+        ));
+        assert!(board.find_pattern(&pattern).is_empty());
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_matching_patterns() {
+        let building_config = BuildingConfig::for_test();
+        let mut board = Board::new(4, 4);
+        assert!(board.matching_patterns(&building_config).is_empty());
+
+        board.place(0, Resource::Wood);
+        board.place(1, Resource::Stone);
+        let ans =
+            [PatternMatch::new(BuildingType::Gray, HashSet::from([0, 1]))];
+        assert_eq!(board.matching_patterns(&building_config), ans);
+
+        // The stone completes a second well and a tavern.
+        board.place(5, Resource::Wood);
+        board.place(8, Resource::Brick);
+        board.place(9, Resource::Brick);
+        board.place(10, Resource::Glass);
+        let ans = [
+            PatternMatch::new(BuildingType::Gray, HashSet::from([0, 1])),
+            PatternMatch::new(BuildingType::Gray, HashSet::from([1, 5])),
+            PatternMatch::new(BuildingType::Green, HashSet::from([8, 9, 10])),
+        ];
+        let matches = board.matching_patterns(&building_config);
+        assert_eq!(matches.len(), ans.len());
+        assert!(ans.iter().all(|m| matches.contains(m)));
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_is_complete() {
+        let building_config = BuildingConfig::for_test();
+        let mut board = Board::new(4, 4);
+        assert!(!board.is_complete(&building_config));
+
+        for idx in 0..board.elems() {
+            board.place(idx, Resource::Stone);
+        }
+        assert!(board.is_complete(&building_config));
+
+        // A well can still be constructed.
+        board.place(0, Resource::Wood);
+        assert!(!board.is_complete(&building_config));
+
+        board.place(0, BuildingType::Gray);
+        assert!(board.is_complete(&building_config));
+    }
+}
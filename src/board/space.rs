@@ -1,6 +1,9 @@
-use strum_macros::EnumIter;
+use serde::{Deserialize, Serialize};
+use strum_macros::{EnumCount, EnumIter};
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(
+    Copy, Clone, Debug, Deserialize, EnumIter, Eq, Hash, PartialEq, Serialize,
+)]
 pub enum Resource {
     Brick,
     Glass,
@@ -9,7 +12,18 @@ pub enum Resource {
     Wood,
 }
 
-#[derive(Copy, Clone, Debug, EnumIter, Eq, Hash, PartialEq)]
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Deserialize,
+    EnumCount,
+    EnumIter,
+    Eq,
+    Hash,
+    PartialEq,
+    Serialize,
+)]
 pub enum BuildingType {
     Black,
     Blue,
@@ -21,7 +35,7 @@ pub enum BuildingType {
     Yellow,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum Space {
     Building(BuildingType),
     BuildingWithOptResource(BuildingType, Option<Resource>),
@@ -80,6 +94,12 @@ pub trait Place {
     fn to_space(self) -> Space;
 }
 
+impl Place for Space {
+    fn to_space(self) -> Space {
+        self
+    }
+}
+
 impl Place for Resource {
     fn to_space(self) -> Space {
         Space::Resource(self)
@@ -0,0 +1,212 @@
+use std::fmt;
+
+use crate::board::space::{BuildingType, Space};
+use crate::board::Board;
+use crate::building_config::{
+    BlackBuilding, BuildingConfig, WAREHOUSE_CAPACITY,
+};
+
+// =============================================================================
+/// A way in which a board breaks the rules and so could not have come from a
+/// real game.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Violation {
+    /// Resources are stored on a building that is not a Warehouse.
+    ResourcesOnNonWarehouse(usize),
+    /// A Warehouse holds more resources than it can.
+    WarehouseOverCapacity { idx: usize, num_resources: usize },
+    /// The town has more than one monument.
+    MultipleMonuments(Vec<usize>),
+    /// A resource is assigned to a building that is not a Factory or a Bank.
+    ResourceOnNonFactoryOrBank(usize),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::ResourcesOnNonWarehouse(idx) => {
+                write!(f, "space #{idx} stores resources but is no warehouse")
+            }
+            Violation::WarehouseOverCapacity { idx, num_resources } => write!(
+                f,
+                "the warehouse on space #{idx} holds {num_resources} \
+                 resources, more than {WAREHOUSE_CAPACITY}"
+            ),
+            Violation::MultipleMonuments(idxs) => {
+                write!(f, "spaces {idxs:?} all hold monuments")
+            }
+            Violation::ResourceOnNonFactoryOrBank(idx) => write!(
+                f,
+                "space #{idx} is assigned a resource but is no factory or bank"
+            ),
+        }
+    }
+}
+
+// =============================================================================
+impl Board {
+    /// Return every rule the board breaks under `building_config`, in order
+    /// of the spaces involved. An empty list means the board is legal.
+    pub fn validate(&self, building_config: &BuildingConfig) -> Vec<Violation> {
+        let is_black = |building_type: &BuildingType| {
+            *building_type == BuildingType::Black
+        };
+        let is_warehouse = |building_type: &BuildingType| {
+            is_black(building_type)
+                && building_config.black() == BlackBuilding::Warehouse
+        };
+        let is_factory_or_bank = |building_type: &BuildingType| {
+            is_black(building_type)
+                && matches!(
+                    building_config.black(),
+                    BlackBuilding::Bank | BlackBuilding::Factory
+                )
+        };
+
+        let mut violations = Vec::new();
+        for (idx, space) in self.spaces.iter().enumerate() {
+            match space {
+                Space::BuildingWithResources(building_type, resources) => {
+                    if !is_warehouse(building_type) {
+                        violations
+                            .push(Violation::ResourcesOnNonWarehouse(idx));
+                    } else if resources.len() > WAREHOUSE_CAPACITY {
+                        violations.push(Violation::WarehouseOverCapacity {
+                            idx,
+                            num_resources: resources.len(),
+                        });
+                    }
+                }
+                Space::BuildingWithOptResource(building_type, _)
+                | Space::BuildingWithResource(building_type, _)
+                    if !is_factory_or_bank(building_type) =>
+                {
+                    violations.push(Violation::ResourceOnNonFactoryOrBank(idx));
+                }
+                _ => (),
+            }
+        }
+
+        let monument_idxs: Vec<usize> = (0..self.elems)
+            .filter(|idx| {
+                self.spaces[*idx].building_type_eq(BuildingType::Magenta)
+            })
+            .collect();
+        if monument_idxs.len() > 1 {
+            violations.push(Violation::MultipleMonuments(monument_idxs));
+        }
+
+        violations
+    }
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::space::Resource;
+    use crate::building_config::generate::Card;
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_validate_legal_board() {
+        let warehouse = BuildingConfig::for_test()
+            .with_card(Card::Black(BlackBuilding::Warehouse));
+        let mut board = Board::new(4, 4);
+        assert!(board.validate(&warehouse).is_empty());
+
+        board.place(0, BuildingType::Magenta);
+        board.place(1, BuildingType::Blue);
+        board.place(2, Resource::Wood);
+        board.place(
+            3,
+            (
+                BuildingType::Black,
+                vec![Resource::Wood, Resource::Glass],
+                3,
+            ),
+        );
+        assert!(board.validate(&warehouse).is_empty());
+
+        let factory = BuildingConfig::for_test()
+            .with_card(Card::Black(BlackBuilding::Factory));
+        board.place(3, (BuildingType::Black, Some(Resource::Stone)));
+        board.place(4, (BuildingType::Black, None));
+        assert!(board.validate(&factory).is_empty());
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_validate_warehouses() {
+        let warehouse = BuildingConfig::for_test()
+            .with_card(Card::Black(BlackBuilding::Warehouse));
+        let mut board = Board::new(4, 4);
+        board.place(0, (BuildingType::Black, vec![Resource::Wood; 4], 3));
+        board.place(5, (BuildingType::Blue, vec![Resource::Wood], 3));
+        assert_eq!(
+            board.validate(&warehouse),
+            vec![
+                Violation::WarehouseOverCapacity {
+                    idx: 0,
+                    num_resources: 4
+                },
+                Violation::ResourcesOnNonWarehouse(5),
+            ]
+        );
+
+        // Black buildings only hold resources when they are Warehouses.
+        let bank = BuildingConfig::for_test()
+            .with_card(Card::Black(BlackBuilding::Bank));
+        assert_eq!(
+            board.validate(&bank),
+            vec![
+                Violation::ResourcesOnNonWarehouse(0),
+                Violation::ResourcesOnNonWarehouse(5),
+            ]
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_validate_opt_resources() {
+        let mut board = Board::new(4, 4);
+        board.place(2, (BuildingType::Black, Some(Resource::Brick)));
+        board.place(6, (BuildingType::Red, None));
+        board.place(7, (BuildingType::Gray, Resource::Glass));
+        let bank = BuildingConfig::for_test()
+            .with_card(Card::Black(BlackBuilding::Bank));
+        assert_eq!(
+            board.validate(&bank),
+            vec![
+                Violation::ResourceOnNonFactoryOrBank(6),
+                Violation::ResourceOnNonFactoryOrBank(7),
+            ]
+        );
+
+        let trading_post = BuildingConfig::for_test()
+            .with_card(Card::Black(BlackBuilding::TradingPost));
+        assert_eq!(
+            board.validate(&trading_post),
+            vec![
+                Violation::ResourceOnNonFactoryOrBank(2),
+                Violation::ResourceOnNonFactoryOrBank(6),
+                Violation::ResourceOnNonFactoryOrBank(7),
+            ]
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_validate_monuments() {
+        let warehouse = BuildingConfig::for_test()
+            .with_card(Card::Black(BlackBuilding::Warehouse));
+        let mut board = Board::new(4, 4);
+        board.place(3, BuildingType::Magenta);
+        board.place(9, BuildingType::Magenta);
+        board.place(12, BuildingType::Magenta);
+        assert_eq!(
+            board.validate(&warehouse),
+            vec![Violation::MultipleMonuments(vec![3, 9, 12])]
+        );
+    }
+}
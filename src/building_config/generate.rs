@@ -0,0 +1,426 @@
+use std::error;
+use std::fmt;
+
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use strum::IntoEnumIterator;
+
+use crate::board::space::BuildingType;
+use crate::building_config::{
+    BlackBuilding, BlueBuilding, BuildingConfig, GrayBuilding, GreenBuilding,
+    MagentaBuilding, OrangeBuilding, RedBuilding, YellowBuilding,
+};
+
+// =============================================================================
+/// A single card from one of the building decks.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Card {
+    Black(BlackBuilding),
+    Blue(BlueBuilding),
+    Gray(GrayBuilding),
+    Green(GreenBuilding),
+    Magenta(MagentaBuilding),
+    Orange(OrangeBuilding),
+    Red(RedBuilding),
+    Yellow(YellowBuilding),
+}
+
+impl Card {
+    /// Return the deck the card belongs to.
+    pub fn building_type(&self) -> BuildingType {
+        match self {
+            Card::Black(_) => BuildingType::Black,
+            Card::Blue(_) => BuildingType::Blue,
+            Card::Gray(_) => BuildingType::Gray,
+            Card::Green(_) => BuildingType::Green,
+            Card::Magenta(_) => BuildingType::Magenta,
+            Card::Orange(_) => BuildingType::Orange,
+            Card::Red(_) => BuildingType::Red,
+            Card::Yellow(_) => BuildingType::Yellow,
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return every card in the deck of `building_type`.
+    pub fn deck(building_type: BuildingType) -> Vec<Card> {
+        let deck = match building_type {
+            BuildingType::Black => {
+                BlackBuilding::iter().map(Card::Black).collect()
+            }
+            BuildingType::Blue => {
+                BlueBuilding::iter().map(Card::Blue).collect()
+            }
+            BuildingType::Gray => {
+                GrayBuilding::iter().map(Card::Gray).collect()
+            }
+            BuildingType::Green => {
+                GreenBuilding::iter().map(Card::Green).collect()
+            }
+            BuildingType::Magenta => {
+                MagentaBuilding::iter().map(Card::Magenta).collect()
+            }
+            BuildingType::Orange => {
+                OrangeBuilding::iter().map(Card::Orange).collect()
+            }
+            BuildingType::Red => RedBuilding::iter().map(Card::Red).collect(),
+            BuildingType::Yellow => {
+                YellowBuilding::iter().map(Card::Yellow).collect()
+            }
+        };
+
+        deck
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return every card of every deck, in the order of `BuildingType`.
+    pub fn all() -> Vec<Card> {
+        BuildingType::iter().flat_map(Card::deck).collect()
+    }
+}
+
+// =============================================================================
+/// Reasons a building config cannot be generated.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GenerateError {
+    /// Two cards from the same deck were forced.
+    ConflictingForcedCards(Card, Card),
+    /// A card was both forced and excluded.
+    ForcedCardExcluded(Card),
+    /// Every card in a deck was excluded.
+    DeckEmpty(BuildingType),
+    /// The share seed could not be read.
+    InvalidShareSeed(String),
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerateError::ConflictingForcedCards(card, other) => {
+                write!(f, "cannot force both {card:?} and {other:?}")
+            }
+            GenerateError::ForcedCardExcluded(card) => {
+                write!(f, "{card:?} is both forced and excluded")
+            }
+            GenerateError::DeckEmpty(building_type) => {
+                write!(f, "every {building_type:?} card is excluded")
+            }
+            GenerateError::InvalidShareSeed(share_seed) => {
+                write!(f, "'{share_seed}' is not a share seed")
+            }
+        }
+    }
+}
+
+impl error::Error for GenerateError {}
+
+// =============================================================================
+/// Draws one card from each deck to build a `BuildingConfig`. The same seed
+/// and options always produce the same config.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Generator {
+    excluded: Vec<Card>,
+    forced: Vec<Card>,
+}
+
+impl Generator {
+    /// Never draw `card`.
+    pub fn exclude(mut self, card: Card) -> Self {
+        self.excluded.push(card);
+        self
+    }
+
+    // -------------------------------------------------------------------------
+    /// Always use `card` for its deck.
+    pub fn force(mut self, card: Card) -> Self {
+        self.forced.push(card);
+        self
+    }
+
+    // -------------------------------------------------------------------------
+    /// Draw a card from each deck, in the order of `BuildingType`, using an RNG
+    /// seeded with `seed`.
+    pub fn generate(&self, seed: u64) -> Result<BuildingConfig, GenerateError> {
+        for (i, card) in self.forced.iter().enumerate() {
+            if self.excluded.contains(card) {
+                return Err(GenerateError::ForcedCardExcluded(*card));
+            }
+            if let Some(other) = self.forced[..i]
+                .iter()
+                .find(|other| other.building_type() == card.building_type())
+            {
+                return Err(GenerateError::ConflictingForcedCards(
+                    *other, *card,
+                ));
+            }
+        }
+
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut building_config = BuildingConfig::new(
+            BlackBuilding::Bank,
+            BlueBuilding::Cottage,
+            GrayBuilding::Fountain,
+            GreenBuilding::Almshouse,
+            MagentaBuilding::ArchitectsGuild,
+            OrangeBuilding::Abbey,
+            RedBuilding::Farm,
+            YellowBuilding::Bakery,
+        );
+        for building_type in BuildingType::iter() {
+            let forced = self
+                .forced
+                .iter()
+                .find(|card| card.building_type() == building_type);
+            let card = match forced {
+                Some(card) => *card,
+                None => {
+                    let deck: Vec<Card> = Card::deck(building_type)
+                        .into_iter()
+                        .filter(|card| !self.excluded.contains(card))
+                        .collect();
+                    *deck
+                        .choose(&mut rng)
+                        .ok_or(GenerateError::DeckEmpty(building_type))?
+                }
+            };
+            building_config = building_config.with_card(card);
+        }
+
+        Ok(building_config)
+    }
+
+    // -------------------------------------------------------------------------
+    /// Draw a config with a new random seed, and return it with the share seed
+    /// that rebuilds it.
+    pub fn generate_random(
+        &self,
+    ) -> Result<(BuildingConfig, String), GenerateError> {
+        let seed = random_seed();
+        let building_config = self.generate(seed)?;
+
+        Ok((building_config, self.to_share_seed(seed)))
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return `seed` and these options in the short form players share with
+    /// each other: the seed in hex, followed by the excluded and forced cards
+    /// as hex bitmasks over `Card::all` if there are any.
+    pub fn to_share_seed(&self, seed: u64) -> String {
+        if self.excluded.is_empty() && self.forced.is_empty() {
+            return format!("{seed:016X}");
+        }
+        let share_seed = format!(
+            "{seed:016X}-{:010X}-{:010X}",
+            cards_to_bits(&self.excluded),
+            cards_to_bits(&self.forced)
+        );
+
+        share_seed
+    }
+
+    // -------------------------------------------------------------------------
+    /// Read a share seed made by `to_share_seed`, returning the generator and
+    /// the seed to pass to `generate`.
+    pub fn from_share_seed(
+        share_seed: &str,
+    ) -> Result<(Generator, u64), GenerateError> {
+        let invalid =
+            || GenerateError::InvalidShareSeed(share_seed.to_string());
+        let parts: Vec<&str> = share_seed.trim().split('-').collect();
+        let (seed, excluded, forced) = match parts[..] {
+            [seed] => (seed, "0", "0"),
+            [seed, excluded, forced] => (seed, excluded, forced),
+            _ => return Err(invalid()),
+        };
+        let seed = u64::from_str_radix(seed, 16).map_err(|_| invalid())?;
+        let excluded = u64::from_str_radix(excluded, 16)
+            .ok()
+            .and_then(bits_to_cards)
+            .ok_or_else(invalid)?;
+        let forced = u64::from_str_radix(forced, 16)
+            .ok()
+            .and_then(bits_to_cards)
+            .ok_or_else(invalid)?;
+
+        Ok((Generator { excluded, forced }, seed))
+    }
+}
+
+// -----------------------------------------------------------------------------
+/// Return the set bits for `cards`, with bit #i for the ith card of
+/// `Card::all`.
+fn cards_to_bits(cards: &[Card]) -> u64 {
+    let bits = Card::all()
+        .iter()
+        .enumerate()
+        .filter(|(_, card)| cards.contains(card))
+        .fold(0, |bits, (i, _)| bits | 1 << i);
+
+    bits
+}
+
+// -----------------------------------------------------------------------------
+/// Return the cards whose bits are set, or `None` if a bit past the last card
+/// is set.
+fn bits_to_cards(bits: u64) -> Option<Vec<Card>> {
+    let all_cards = Card::all();
+    if bits >> all_cards.len() != 0 {
+        return None;
+    }
+    let cards = all_cards
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| bits & 1 << i != 0)
+        .map(|(_, card)| card)
+        .collect();
+
+    Some(cards)
+}
+
+// =============================================================================
+impl BuildingConfig {
+    /// Return a copy of this config with `card` in place of the card from the
+    /// same deck.
+    pub fn with_card(&self, card: Card) -> Self {
+        let mut building_config = self.clone();
+        match card {
+            Card::Black(black) => building_config.black = black,
+            Card::Blue(blue) => building_config.blue = blue,
+            Card::Gray(gray) => building_config.gray = gray,
+            Card::Green(green) => building_config.green = green,
+            Card::Magenta(magenta) => building_config.magenta = magenta,
+            Card::Orange(orange) => building_config.orange = orange,
+            Card::Red(red) => building_config.red = red,
+            Card::Yellow(yellow) => building_config.yellow = yellow,
+        }
+
+        building_config
+    }
+}
+
+// -----------------------------------------------------------------------------
+/// Return a new seed for `Generator::generate`.
+pub fn random_seed() -> u64 {
+    rand::random()
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_deck() {
+        assert_eq!(Card::deck(BuildingType::Black).len(), 4);
+        assert_eq!(Card::deck(BuildingType::Blue).len(), 1);
+        assert_eq!(Card::deck(BuildingType::Magenta).len(), 15);
+        assert!(Card::deck(BuildingType::Red)
+            .iter()
+            .all(|card| card.building_type() == BuildingType::Red));
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_generate() {
+        let generator = Generator::default();
+        assert_eq!(generator.generate(42), generator.generate(42));
+
+        // Different seeds eventually give different configs.
+        let building_config = generator.generate(42).unwrap();
+        assert!((0..100)
+            .any(|seed| generator.generate(seed).unwrap() != building_config));
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_generate_with_options() {
+        let generator = Generator::default()
+            .exclude(Card::Red(RedBuilding::Farm))
+            .exclude(Card::Red(RedBuilding::Granary))
+            .exclude(Card::Red(RedBuilding::Orchard))
+            .force(Card::Magenta(MagentaBuilding::TheSkyBaths));
+        for seed in 0..20 {
+            let building_config = generator.generate(seed).unwrap();
+            assert_eq!(building_config.red(), RedBuilding::Greenhouse);
+            assert_eq!(building_config.magenta(), MagentaBuilding::TheSkyBaths);
+        }
+
+        let generator = generator.exclude(Card::Red(RedBuilding::Greenhouse));
+        assert_eq!(
+            generator.generate(0),
+            Err(GenerateError::DeckEmpty(BuildingType::Red))
+        );
+
+        let generator = Generator::default()
+            .force(Card::Gray(GrayBuilding::Well))
+            .force(Card::Gray(GrayBuilding::Shed));
+        assert_eq!(
+            generator.generate(0),
+            Err(GenerateError::ConflictingForcedCards(
+                Card::Gray(GrayBuilding::Well),
+                Card::Gray(GrayBuilding::Shed)
+            ))
+        );
+
+        let generator = Generator::default()
+            .force(Card::Gray(GrayBuilding::Well))
+            .exclude(Card::Gray(GrayBuilding::Well));
+        assert_eq!(
+            generator.generate(0),
+            Err(GenerateError::ForcedCardExcluded(Card::Gray(
+                GrayBuilding::Well
+            )))
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_share_seed() {
+        let seed = 0x1234_ABCD_0000_FFFF;
+        let generator = Generator::default();
+        assert_eq!(generator.to_share_seed(seed), "1234ABCD0000FFFF");
+        assert_eq!(
+            Generator::from_share_seed("1234ABCD0000FFFF"),
+            Ok((generator.clone(), seed))
+        );
+        assert_eq!(
+            Generator::from_share_seed("1234abcd0000ffff"),
+            Ok((generator, seed))
+        );
+
+        // The options travel with the seed, so the same config comes back.
+        let generator = Generator::default()
+            .exclude(Card::Red(RedBuilding::Farm))
+            .exclude(Card::Yellow(YellowBuilding::Tailor))
+            .force(Card::Magenta(MagentaBuilding::TheSkyBaths));
+        let share_seed = generator.to_share_seed(seed);
+        assert_eq!(share_seed, "1234ABCD0000FFFF-4100000000-0004000000");
+        let (shared_generator, shared_seed) =
+            Generator::from_share_seed(&share_seed).unwrap();
+        assert_eq!(shared_seed, seed);
+        for seed in 0..20 {
+            assert_eq!(
+                shared_generator.generate(seed),
+                generator.generate(seed)
+            );
+        }
+
+        let (building_config, share_seed) =
+            generator.generate_random().unwrap();
+        let (shared_generator, shared_seed) =
+            Generator::from_share_seed(&share_seed).unwrap();
+        assert_eq!(shared_generator.generate(shared_seed), Ok(building_config));
+
+        for share_seed in [
+            "tiny towns",
+            "1234ABCD0000FFFF-8000010000",
+            "1234ABCD0000FFFF-FFFFFFFFFFF-0",
+        ] {
+            assert_eq!(
+                Generator::from_share_seed(share_seed),
+                Err(GenerateError::InvalidShareSeed(share_seed.to_string()))
+            );
+        }
+    }
+}
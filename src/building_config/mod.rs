@@ -1,4 +1,14 @@
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+pub mod generate;
+
+/// The most resources a Warehouse can hold.
+pub const WAREHOUSE_CAPACITY: usize = 3;
+
+#[derive(
+    Copy, Clone, Debug, Deserialize, EnumIter, Eq, PartialEq, Serialize,
+)]
 pub enum BlackBuilding {
     Bank,
     Factory,
@@ -6,12 +16,16 @@ pub enum BlackBuilding {
     Warehouse,
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(
+    Copy, Clone, Debug, Deserialize, EnumIter, Eq, PartialEq, Serialize,
+)]
 pub enum BlueBuilding {
     Cottage,
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(
+    Copy, Clone, Debug, Deserialize, EnumIter, Eq, PartialEq, Serialize,
+)]
 pub enum GrayBuilding {
     Fountain,
     Millstone,
@@ -19,7 +33,9 @@ pub enum GrayBuilding {
     Well,
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(
+    Copy, Clone, Debug, Deserialize, EnumIter, Eq, PartialEq, Serialize,
+)]
 pub enum GreenBuilding {
     Almshouse,
     FeastHall,
@@ -27,7 +43,9 @@ pub enum GreenBuilding {
     Tavern,
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(
+    Copy, Clone, Debug, Deserialize, EnumIter, Eq, PartialEq, Serialize,
+)]
 pub enum MagentaBuilding {
     ArchitectsGuild,
     ArchiveOfTheSecondAge,
@@ -46,7 +64,9 @@ pub enum MagentaBuilding {
     TheStarloom,
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(
+    Copy, Clone, Debug, Deserialize, EnumIter, Eq, PartialEq, Serialize,
+)]
 pub enum OrangeBuilding {
     Abbey,
     Chapel,
@@ -54,7 +74,9 @@ pub enum OrangeBuilding {
     Temple,
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(
+    Copy, Clone, Debug, Deserialize, EnumIter, Eq, PartialEq, Serialize,
+)]
 pub enum RedBuilding {
     Farm,
     Granary,
@@ -62,7 +84,9 @@ pub enum RedBuilding {
     Orchard,
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(
+    Copy, Clone, Debug, Deserialize, EnumIter, Eq, PartialEq, Serialize,
+)]
 pub enum YellowBuilding {
     Bakery,
     Market,
@@ -70,6 +94,7 @@ pub enum YellowBuilding {
     Theater,
 }
 
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct BuildingConfig {
     black: BlackBuilding,
     blue: BlueBuilding,
@@ -128,4 +153,32 @@ impl BuildingConfig {
     pub fn yellow(&self) -> YellowBuilding {
         self.yellow
     }
+
+    /// Return a copy of this config with `magenta` as the monument. Each
+    /// player holds their own monument, so each town is scored with its own
+    /// config.
+    pub fn with_magenta(&self, magenta: MagentaBuilding) -> Self {
+        Self {
+            magenta,
+            ..self.clone()
+        }
+    }
+}
+
+// =============================================================================
+#[cfg(test)]
+impl BuildingConfig {
+    /// A config for tests to start from, with one card from each deck.
+    pub fn for_test() -> Self {
+        Self::new(
+            BlackBuilding::Warehouse,
+            BlueBuilding::Cottage,
+            GrayBuilding::Well,
+            GreenBuilding::Tavern,
+            MagentaBuilding::SilvaForum,
+            OrangeBuilding::Chapel,
+            RedBuilding::Farm,
+            YellowBuilding::Tailor,
+        )
+    }
 }
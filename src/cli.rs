@@ -0,0 +1,520 @@
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use strum::IntoEnumIterator;
+
+use crate::board::space::BuildingType;
+use crate::board::validate::Violation;
+use crate::board::Board;
+use crate::building_config::generate::Card;
+use crate::building_config::{
+    BlackBuilding, BlueBuilding, BuildingConfig, GrayBuilding, GreenBuilding,
+    MagentaBuilding, OrangeBuilding, RedBuilding, YellowBuilding,
+};
+use crate::error::Error;
+use crate::schema::{from_json, to_json};
+use crate::score::explain::Reason;
+use crate::score::feed;
+use crate::score::{explain, try_score, ScoreCard, ScoringContext};
+use crate::tui::{self, App};
+
+pub const USAGE: &str = "\
+Usage: tiny-towns-rust BOARD [OPTIONS]
+
+Score the town in BOARD, written in board notation or as JSON.
+
+Options:
+  --config FILE      read the building config from FILE, written as JSON
+  --black NAME       use the NAME card for black buildings, and likewise for
+  --blue NAME        the other decks; these override the config file, and
+  --gray NAME        all eight are needed without one
+  --green NAME
+  --magenta NAME
+  --orange NAME
+  --red NAME
+  --yellow NAME
+  --neighbor BOARD   score Feast Halls against the town in BOARD
+  --json             print the score card as JSON
+  --explain          print why each space scored what it did, and which
+                     red building fed each building
+  --tui              edit the town in BOARD full-screen, starting a new 4x4
+                     town if BOARD does not exist yet
+  -h, --help         print this message";
+
+// =============================================================================
+/// Reasons the command line cannot be carried out.
+#[derive(Debug)]
+pub enum CliError {
+    /// The arguments are not understood.
+    Usage(String),
+    /// A file or the terminal cannot be read or written.
+    Io { path: String, msg: String },
+    /// A file or the scoring of the town is invalid.
+    Invalid { path: String, err: Error },
+    /// The town breaks the rules of the game.
+    Violations(Vec<Violation>),
+}
+
+impl CliError {
+    /// Return the exit code to report the error with.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Usage(_) => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Usage(msg) => write!(f, "{msg}\n\n{USAGE}"),
+            CliError::Io { path, msg } => {
+                write!(f, "cannot read {path}: {msg}")
+            }
+            CliError::Invalid { path, err } => write!(f, "{path}: {err}"),
+            CliError::Violations(violations) => {
+                write!(f, "the town breaks the rules:")?;
+                for violation in violations {
+                    write!(f, "\n  {violation}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl error::Error for CliError {}
+
+// =============================================================================
+/// What the command line asks for.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Command {
+    Help,
+    Score(Args),
+    Edit(Args),
+}
+
+/// The options for scoring a town.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Args {
+    board_path: String,
+    config_path: Option<String>,
+    cards: Vec<Card>,
+    neighbor_path: Option<String>,
+    json: bool,
+    explain: bool,
+}
+
+// -----------------------------------------------------------------------------
+/// Return the card named `name` from the deck of `building_type`. Names are
+/// compared ignoring case, spaces and punctuation, so `trading-post` and
+/// `Trading Post` both name the Trading Post.
+pub fn parse_card(building_type: BuildingType, name: &str) -> Option<Card> {
+    let normalize = |name: &str| -> String {
+        name.chars()
+            .filter(|c| c.is_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect()
+    };
+    let card = Card::deck(building_type).into_iter().find(|card| {
+        let card_name = match card {
+            Card::Black(building) => format!("{building:?}"),
+            Card::Blue(building) => format!("{building:?}"),
+            Card::Gray(building) => format!("{building:?}"),
+            Card::Green(building) => format!("{building:?}"),
+            Card::Magenta(building) => format!("{building:?}"),
+            Card::Orange(building) => format!("{building:?}"),
+            Card::Red(building) => format!("{building:?}"),
+            Card::Yellow(building) => format!("{building:?}"),
+        };
+        normalize(&card_name) == normalize(name)
+    });
+
+    card
+}
+
+// -----------------------------------------------------------------------------
+/// Return the deck named by a card flag such as `--red`.
+fn card_flag(flag: &str) -> Option<BuildingType> {
+    let building_type = BuildingType::iter().find(|building_type| {
+        flag == format!("--{building_type:?}").to_lowercase()
+    });
+
+    building_type
+}
+
+// -----------------------------------------------------------------------------
+/// Parse the command line arguments, not including the program name.
+pub fn parse_args<I>(args: I) -> Result<Command, CliError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut board_path = None;
+    let mut tui = false;
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| {
+            args.next()
+                .ok_or_else(|| CliError::Usage(format!("{flag} needs a value")))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--config" => parsed.config_path = Some(value(&arg)?),
+            "--neighbor" => parsed.neighbor_path = Some(value(&arg)?),
+            "--json" => parsed.json = true,
+            "--explain" => parsed.explain = true,
+            "--tui" => tui = true,
+            flag if card_flag(flag).is_some() => {
+                let building_type = card_flag(flag).unwrap();
+                let name = value(flag)?;
+                let card =
+                    parse_card(building_type, &name).ok_or_else(|| {
+                        CliError::Usage(format!(
+                            "'{name}' is not a {building_type:?} building"
+                        ))
+                    })?;
+                parsed
+                    .cards
+                    .retain(|other| other.building_type() != building_type);
+                parsed.cards.push(card);
+            }
+            flag if flag.starts_with('-') => {
+                return Err(CliError::Usage(format!("unknown option {flag}")))
+            }
+            _ if board_path.is_none() => board_path = Some(arg),
+            _ => {
+                return Err(CliError::Usage(format!(
+                    "unexpected argument {arg}"
+                )))
+            }
+        }
+    }
+
+    parsed.board_path = board_path
+        .ok_or_else(|| CliError::Usage(String::from("no board given")))?;
+    if parsed.config_path.is_none() {
+        let missing: Vec<String> = BuildingType::iter()
+            .filter(|building_type| {
+                parsed
+                    .cards
+                    .iter()
+                    .all(|card| card.building_type() != *building_type)
+            })
+            .map(|building_type| format!("--{building_type:?}").to_lowercase())
+            .collect();
+        if !missing.is_empty() {
+            return Err(CliError::Usage(format!(
+                "no config file given, and missing {}",
+                missing.join(", ")
+            )));
+        }
+    }
+
+    if parsed.json && parsed.explain {
+        return Err(CliError::Usage(String::from(
+            "--explain cannot be used with --json",
+        )));
+    }
+
+    let command = if tui {
+        Command::Edit(parsed)
+    } else {
+        Command::Score(parsed)
+    };
+
+    Ok(command)
+}
+
+// -----------------------------------------------------------------------------
+/// Read a board written either as JSON or in board notation.
+pub fn read_board(contents: &str) -> Result<Board, Error> {
+    let board = if contents.trim_start().starts_with('{') {
+        from_json(contents)?
+    } else {
+        Board::from_notation(contents)?
+    };
+
+    Ok(board)
+}
+
+// -----------------------------------------------------------------------------
+fn read_file(path: &str) -> Result<String, CliError> {
+    fs::read_to_string(path).map_err(|err| CliError::Io {
+        path: path.to_string(),
+        msg: err.to_string(),
+    })
+}
+
+// -----------------------------------------------------------------------------
+fn read_board_file(path: &str) -> Result<Board, CliError> {
+    let board =
+        read_board(&read_file(path)?).map_err(|err| CliError::Invalid {
+            path: path.to_string(),
+            err,
+        })?;
+
+    Ok(board)
+}
+
+// -----------------------------------------------------------------------------
+/// Return the building config from the config file, with any cards named on
+/// the command line in place of its own.
+fn building_config(args: &Args) -> Result<BuildingConfig, CliError> {
+    let base = match &args.config_path {
+        Some(path) => {
+            from_json(&read_file(path)?).map_err(|err| CliError::Invalid {
+                path: path.to_string(),
+                err: Error::from(err),
+            })?
+        }
+        // Every deck has a card named on the command line, so each of these
+        // will be replaced.
+        None => BuildingConfig::new(
+            BlackBuilding::Bank,
+            BlueBuilding::Cottage,
+            GrayBuilding::Fountain,
+            GreenBuilding::Almshouse,
+            MagentaBuilding::ArchitectsGuild,
+            OrangeBuilding::Abbey,
+            RedBuilding::Farm,
+            YellowBuilding::Bakery,
+        ),
+    };
+    let building_config =
+        args.cards.iter().fold(base, |building_config, card| {
+            building_config.with_card(*card)
+        });
+
+    Ok(building_config)
+}
+
+// -----------------------------------------------------------------------------
+/// Read and check the input named by `args`, and score and explain the town.
+pub fn score_args(
+    args: &Args,
+) -> Result<(Board, BuildingConfig, ScoreCard, HashMap<usize, Reason>), CliError>
+{
+    let building_config = building_config(args)?;
+    let board = read_board_file(&args.board_path)?;
+    let neighbor = match &args.neighbor_path {
+        Some(path) => Some(read_board_file(path)?),
+        None => None,
+    };
+
+    let violations = board.validate(&building_config);
+    if !violations.is_empty() {
+        return Err(CliError::Violations(violations));
+    }
+
+    let scoring_context = ScoringContext::default();
+    let score_card = try_score(
+        &board,
+        &building_config,
+        &scoring_context,
+        neighbor.as_ref(),
+    )
+    .map_err(|err| CliError::Invalid {
+        path: args.board_path.clone(),
+        err,
+    })?;
+    let reasons = explain(
+        &board,
+        &building_config,
+        &scoring_context,
+        neighbor.as_ref(),
+    );
+
+    Ok((board, building_config, score_card, reasons))
+}
+
+// -----------------------------------------------------------------------------
+/// Open the town named by `args` in the full-screen editor.
+fn edit(args: &Args) -> Result<(), CliError> {
+    let building_config = building_config(args)?;
+    let board = if Path::new(&args.board_path).exists() {
+        read_board_file(&args.board_path)?
+    } else {
+        Board::new(4, 4)
+    };
+
+    let app = App::new(board, building_config, Some(args.board_path.clone()));
+    tui::run(app).map_err(|err| CliError::Io {
+        path: String::from("the terminal"),
+        msg: err.to_string(),
+    })
+}
+
+// -----------------------------------------------------------------------------
+/// Carry out the command line, printing the result to stdout.
+pub fn run<I>(args: I) -> Result<(), CliError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let args = match parse_args(args)? {
+        Command::Help => {
+            println!("{USAGE}");
+            return Ok(());
+        }
+        Command::Score(args) => args,
+        Command::Edit(args) => return edit(&args),
+    };
+
+    let (board, building_config, score_card, reasons) = score_args(&args)?;
+    if args.json {
+        let json = to_json(&score_card).map_err(|err| CliError::Invalid {
+            path: args.board_path.clone(),
+            err: Error::from(err),
+        })?;
+        println!("{json}");
+    } else {
+        board.print_scores(&score_card, &building_config);
+        if args.explain {
+            println!();
+            board.print_explanations(&score_card, &reasons, &building_config);
+            let report = feed::report(
+                &board,
+                &building_config,
+                &ScoringContext::default(),
+            );
+            println!();
+            board.print_feeding(&report, &building_config);
+        }
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::space::{Resource, Space};
+
+    // -------------------------------------------------------------------------
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_parse_card() {
+        assert_eq!(
+            parse_card(BuildingType::Black, "trading-post"),
+            Some(Card::Black(BlackBuilding::TradingPost))
+        );
+        assert_eq!(
+            parse_card(BuildingType::Magenta, "Opaleye's Watch"),
+            Some(Card::Magenta(MagentaBuilding::OpaleyesWatch))
+        );
+        assert_eq!(parse_card(BuildingType::Red, "tavern"), None);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_parse_args() {
+        let command = parse_args(args(&[
+            "town.txt",
+            "--config",
+            "config.json",
+            "--red",
+            "farm",
+            "--red",
+            "orchard",
+            "--neighbor",
+            "left.txt",
+            "--json",
+        ]))
+        .unwrap();
+        assert_eq!(
+            command,
+            Command::Score(Args {
+                board_path: String::from("town.txt"),
+                config_path: Some(String::from("config.json")),
+                cards: vec![Card::Red(RedBuilding::Orchard)],
+                neighbor_path: Some(String::from("left.txt")),
+                json: true,
+                explain: false,
+            })
+        );
+        assert_eq!(parse_args(args(&["--help"])).unwrap(), Command::Help);
+
+        let command = parse_args(args(&["town.txt", "--tui", "--config", "c"]));
+        assert!(matches!(command, Ok(Command::Edit(_))));
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_parse_args_errors() {
+        let usage = |args| match parse_args(args) {
+            Err(CliError::Usage(msg)) => msg,
+            _ => panic!("expected a usage error"),
+        };
+        assert_eq!(usage(args(&[])), "no board given");
+        assert_eq!(usage(args(&["a", "b"])), "unexpected argument b");
+        assert_eq!(usage(args(&["a", "--config"])), "--config needs a value");
+        assert_eq!(usage(args(&["a", "--fast"])), "unknown option --fast");
+        assert_eq!(
+            usage(args(&["a", "--config", "c", "--json", "--explain"])),
+            "--explain cannot be used with --json"
+        );
+        assert_eq!(
+            usage(args(&["a", "--green", "farm"])),
+            "'farm' is not a Green building"
+        );
+        assert_eq!(
+            usage(args(&["a", "--green", "inn", "--red", "farm"])),
+            "no config file given, and missing --black, --blue, --gray, \
+             --magenta, --orange, --yellow"
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_building_config() {
+        let parsed = parse_args(args(&[
+            "town.txt",
+            "--black",
+            "bank",
+            "--blue",
+            "cottage",
+            "--gray",
+            "well",
+            "--green",
+            "inn",
+            "--magenta",
+            "silva forum",
+            "--orange",
+            "chapel",
+            "--red",
+            "farm",
+            "--yellow",
+            "tailor",
+        ]));
+        let args = match parsed.unwrap() {
+            Command::Score(args) => args,
+            _ => panic!("expected scoring arguments"),
+        };
+        let building_config = building_config(&args).unwrap();
+        assert_eq!(building_config.green(), GreenBuilding::Inn);
+        assert_eq!(building_config.magenta(), MagentaBuilding::SilvaForum);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_read_board() {
+        let board = read_board("B . .\n. w .\n. . K()").unwrap();
+        assert_eq!(board.spaces()[4], Space::Resource(Resource::Wood));
+
+        let json = to_json(&board).unwrap();
+        let parsed = read_board(&json).unwrap();
+        assert_eq!(parsed.spaces(), board.spaces());
+
+        assert!(read_board("B . .\n. w").is_err());
+        assert!(read_board("{\"schema_version\":1}").is_err());
+    }
+}
@@ -8,6 +8,8 @@ use crate::building_config::{
     BlackBuilding, BlueBuilding, BuildingConfig, GrayBuilding, GreenBuilding,
     MagentaBuilding, OrangeBuilding, RedBuilding, YellowBuilding,
 };
+use crate::score::explain::Reason;
+use crate::score::feed::{Feeder, FeedingReport};
 use crate::score::ScoreCard;
 
 // =============================================================================
@@ -158,6 +160,21 @@ impl YellowBuilding {
     }
 }
 
+// -----------------------------------------------------------------------------
+/// Color `symbol` in the color of `building_type`.
+pub fn colorize(building_type: BuildingType, symbol: &str) -> ColoredString {
+    match building_type {
+        BuildingType::Orange => symbol.truecolor(230, 131, 2),
+        BuildingType::Blue => symbol.blue(),
+        BuildingType::Black => symbol.truecolor(10, 10, 10),
+        BuildingType::Red => symbol.red(),
+        BuildingType::Green => symbol.green(),
+        BuildingType::Yellow => symbol.yellow(),
+        BuildingType::Gray => symbol.truecolor(75, 75, 75),
+        BuildingType::Magenta => symbol.magenta(),
+    }
+}
+
 // =============================================================================
 impl Space {
     pub fn as_str(
@@ -175,16 +192,7 @@ impl Space {
                 } else {
                     String::from("@")
                 };
-                match building_type {
-                    BuildingType::Orange => symbol.truecolor(230, 131, 2),
-                    BuildingType::Blue => symbol.blue(),
-                    BuildingType::Black => symbol.truecolor(10, 10, 10),
-                    BuildingType::Red => symbol.red(),
-                    BuildingType::Green => symbol.green(),
-                    BuildingType::Yellow => symbol.yellow(),
-                    BuildingType::Gray => symbol.truecolor(75, 75, 75),
-                    BuildingType::Magenta => symbol.magenta(),
-                }
+                colorize(*building_type, &symbol)
             }
             Space::Resource(resource) => {
                 let symbol = if let Some(scores) = scores_opt {
@@ -208,7 +216,112 @@ impl Space {
     }
 }
 
-fn make_messages(
+// -----------------------------------------------------------------------------
+/// Return the name of the `building_type` building in `building_config`.
+fn building_name(
+    building_config: &BuildingConfig,
+    building_type: BuildingType,
+) -> String {
+    let name = match building_type {
+        BuildingType::Black => building_config.black().to_string(),
+        BuildingType::Blue => building_config.blue().to_string(),
+        BuildingType::Gray => building_config.gray().to_string(),
+        BuildingType::Green => building_config.green().to_string(),
+        BuildingType::Magenta => building_config.magenta().to_string(),
+        BuildingType::Orange => building_config.orange().to_string(),
+        BuildingType::Red => building_config.red().to_string(),
+        BuildingType::Yellow => building_config.yellow().to_string(),
+    };
+
+    name
+}
+
+// -----------------------------------------------------------------------------
+/// Return the position of the space at `idx`, counting rows and columns from
+/// one, as players do.
+fn position(board: &Board, idx: usize) -> String {
+    let position =
+        format!("row {}, col {}", board.row(idx) + 1, board.col(idx) + 1);
+
+    position
+}
+
+// -----------------------------------------------------------------------------
+/// Return one line per scored space, in board order, giving its position,
+/// what is on it, its score and the reason for the score.
+pub fn make_explanations(
+    board: &Board,
+    score_card: &ScoreCard,
+    reasons: &HashMap<usize, Reason>,
+    building_config: &BuildingConfig,
+) -> Vec<String> {
+    let scores = score_card.flatten();
+    let explanations = (0..board.elems())
+        .filter_map(|idx| {
+            let points = scores.get(&idx)?;
+            let reason = reasons.get(&idx)?;
+            let name = match board.spaces()[idx].building_type() {
+                Some(building_type) => {
+                    let name = building_name(building_config, building_type);
+                    colorize(building_type, &name).to_string()
+                }
+                None => String::from("Unused space"),
+            };
+            Some(format!(
+                "{}: {name} scored {points}: {reason}",
+                position(board, idx)
+            ))
+        })
+        .collect();
+
+    explanations
+}
+
+// -----------------------------------------------------------------------------
+/// Return one line per feedable building, in board order, giving what fed it
+/// or that it went unfed.
+pub fn make_feeding_messages(
+    board: &Board,
+    report: &FeedingReport,
+    building_config: &BuildingConfig,
+) -> Vec<String> {
+    let red = building_config.red().to_string();
+    let positions = |idxs: &[usize]| -> String {
+        idxs.iter()
+            .map(|idx| position(board, *idx))
+            .collect::<Vec<String>>()
+            .join(" and ")
+    };
+    let messages = (0..board.elems())
+        .filter_map(|idx| {
+            let building_type = board.spaces()[idx].building_type()?;
+            let name = building_name(building_config, building_type);
+            let fed_by = match report.fed().get(&idx) {
+                Some(Feeder::Farm(farm)) => {
+                    format!("fed by the {red} at {}", position(board, *farm))
+                }
+                Some(Feeder::Granaries(idxs) | Feeder::Orchards(idxs)) => {
+                    format!("fed by the {red} at {}", positions(idxs))
+                }
+                Some(Feeder::Greenhouse { greenhouse, group }) => format!(
+                    "fed by the {red} at {} with a group of {}",
+                    position(board, *greenhouse),
+                    group.len()
+                ),
+                None if report.unfed().contains(&idx) => {
+                    String::from("not fed")
+                }
+                None => return None,
+            };
+            Some(format!("{}: {name} {fed_by}", position(board, idx)))
+        })
+        .collect();
+
+    messages
+}
+
+// -----------------------------------------------------------------------------
+pub fn make_messages(
     score_card: &ScoreCard,
     building_config: &BuildingConfig,
 ) -> Vec<String> {
@@ -332,4 +445,27 @@ impl Board {
         }
         println!("Total score: {}", score_card.score_all());
     }
+
+    pub fn print_explanations(
+        &self,
+        score_card: &ScoreCard,
+        reasons: &HashMap<usize, Reason>,
+        building_config: &BuildingConfig,
+    ) {
+        for explanation in
+            make_explanations(self, score_card, reasons, building_config)
+        {
+            println!("{explanation}");
+        }
+    }
+
+    pub fn print_feeding(
+        &self,
+        report: &FeedingReport,
+        building_config: &BuildingConfig,
+    ) {
+        for message in make_feeding_messages(self, report, building_config) {
+            println!("{message}");
+        }
+    }
 }
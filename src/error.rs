@@ -0,0 +1,124 @@
+use std::error;
+use std::fmt;
+
+use crate::board::construct::ConstructError;
+use crate::board::notation::NotationError;
+use crate::building_config::generate::GenerateError;
+use crate::game::GameError;
+use crate::schema::SchemaError;
+
+// =============================================================================
+/// Reasons an operation on a board, a game, or a score may fail. Returned by
+/// the `try_` versions of functions that would otherwise panic, so that bad
+/// input can be reported instead.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// A board must have more than two rows and more than two columns.
+    BoardTooSmall {
+        rows: usize,
+        cols: usize,
+    },
+    /// The board has no four center spaces because it has an odd number of
+    /// rows or columns.
+    NoCenter {
+        rows: usize,
+        cols: usize,
+    },
+    /// The space does not exist on the board.
+    IdxOutOfRange(usize),
+    /// The space does not hold a building.
+    NotABuilding(usize),
+    /// The space is not a Factory or Bank waiting for a resource.
+    NotAwaitingResource(usize),
+    /// The space is not a building that stores resources.
+    NotAWarehouse(usize),
+    /// The player does not exist.
+    NoSuchPlayer(usize),
+    /// A board was given a different number of spaces than it has.
+    WrongNumberOfSpaces {
+        expected: usize,
+        found: usize,
+    },
+    /// Each town must be scored with exactly one config.
+    WrongNumberOfConfigs {
+        expected: usize,
+        found: usize,
+    },
+    /// A scoring table does not score zero buildings as zero points.
+    InvalidScoringTable(String),
+    /// The Shrine of the Elder Tree on this space has no record of how many
+    /// buildings the town had when it was built, so it cannot be scored.
+    NoBuildingsWhenBuilt(usize),
+    Construct(ConstructError),
+    Game(GameError),
+    Generate(GenerateError),
+    Notation(NotationError),
+    Schema(SchemaError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::BoardTooSmall { rows, cols } => {
+                write!(f, "a {rows}x{cols} board is too small")
+            }
+            Error::NoCenter { rows, cols } => {
+                write!(f, "a {rows}x{cols} board has no center")
+            }
+            Error::IdxOutOfRange(idx) => {
+                write!(f, "space #{idx} is not on the board")
+            }
+            Error::NotABuilding(idx) => {
+                write!(f, "space #{idx} does not hold a building")
+            }
+            Error::NotAwaitingResource(idx) => {
+                write!(f, "space #{idx} is not waiting for a resource")
+            }
+            Error::NotAWarehouse(idx) => {
+                write!(f, "space #{idx} cannot store resources")
+            }
+            Error::NoSuchPlayer(player) => {
+                write!(f, "there is no player #{player}")
+            }
+            Error::WrongNumberOfSpaces { expected, found } => {
+                write!(f, "expected {expected} spaces, found {found}")
+            }
+            Error::WrongNumberOfConfigs { expected, found } => {
+                write!(f, "expected {expected} configs, found {found}")
+            }
+            Error::InvalidScoringTable(table) => {
+                write!(f, "{table} must score zero buildings as zero points")
+            }
+            Error::NoBuildingsWhenBuilt(idx) => write!(
+                f,
+                "the shrine on space #{idx} has no record of the number of \
+                buildings when it was built"
+            ),
+            Error::Construct(err) => err.fmt(f),
+            Error::Game(err) => err.fmt(f),
+            Error::Generate(err) => err.fmt(f),
+            Error::Notation(err) => err.fmt(f),
+            Error::Schema(err) => err.fmt(f),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<ConstructError> for Error {
+    fn from(err: ConstructError) -> Self {
+        Error::Construct(err)
+    }
+}
+
+impl From<GameError> for Error {
+    fn from(err: GameError) -> Self {
+        Error::Game(err)
+    }
+}
+
+impl From<GenerateError> for Error {
+    fn from(err: GenerateError) -> Self {
+        Error::Generate(err)
+    }
+}
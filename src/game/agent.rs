@@ -0,0 +1,55 @@
+use crate::board::pattern::PatternMatch;
+use crate::board::space::Resource;
+use crate::building_config::MagentaBuilding;
+use crate::game::black::Placement;
+use crate::game::monument::{PendingEffect, Resolution};
+use crate::game::Game;
+
+// =============================================================================
+/// The decisions a player makes during a round. Implement this to drive a
+/// player from a terminal, a network connection or a simulated strategy.
+pub trait Agent {
+    /// Choose the resource to name as master builder.
+    fn name_resource(&mut self, game: &Game, player: usize) -> Resource;
+
+    /// Choose where to place `resource`.
+    fn place_resource(
+        &mut self,
+        game: &Game,
+        player: usize,
+        resource: Resource,
+    ) -> Placement;
+
+    /// Choose a pattern to construct and the space to construct it on, or
+    /// `None` to stop constructing for the round.
+    fn construct(
+        &mut self,
+        game: &Game,
+        player: usize,
+    ) -> Option<(PatternMatch, usize)>;
+
+    /// Choose the resource to put on the newly constructed Factory or Bank on
+    /// space #idx.
+    fn assign_resource(
+        &mut self,
+        game: &Game,
+        player: usize,
+        idx: usize,
+    ) -> Resource;
+
+    /// Choose how to resolve `effect`, the player's oldest pending effect.
+    fn resolve_effect(
+        &mut self,
+        game: &Game,
+        player: usize,
+        effect: PendingEffect,
+    ) -> Resolution;
+
+    /// Choose the monument to keep from the player's hand.
+    fn keep_monument(
+        &mut self,
+        game: &Game,
+        player: usize,
+        hand: &[MagentaBuilding],
+    ) -> MagentaBuilding;
+}
@@ -0,0 +1,462 @@
+use std::collections::HashSet;
+
+use crate::board::space::{BuildingType, Resource, Space};
+use crate::board::Board;
+use crate::building_config::{
+    BlackBuilding, BuildingConfig, WAREHOUSE_CAPACITY,
+};
+use crate::game::{Game, GameError};
+
+// =============================================================================
+/// Where a player puts this round's resource.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Placement {
+    /// The named resource on empty space #idx.
+    Space(usize),
+    /// `resource` instead of the named resource on empty space `idx`, using a
+    /// Factory that holds the named resource.
+    Factory { resource: Resource, idx: usize },
+    /// The named resource on the Warehouse on space #idx.
+    Warehouse(usize),
+    /// The named resource on the Warehouse on space `warehouse`, in exchange
+    /// for `resource`, which is placed on empty space `idx`.
+    WarehouseSwap {
+        warehouse: usize,
+        resource: Resource,
+        idx: usize,
+    },
+}
+
+// -----------------------------------------------------------------------------
+/// Return the resources on the Banks in a town. The owner of a bank may not
+/// name these resources as master builder.
+pub fn bank_resources(
+    board: &Board,
+    building_config: &BuildingConfig,
+) -> HashSet<Resource> {
+    let resources = if building_config.black() == BlackBuilding::Bank {
+        board
+            .spaces()
+            .iter()
+            .filter_map(|space| match space {
+                Space::BuildingWithOptResource(
+                    BuildingType::Black,
+                    resource,
+                ) => *resource,
+                _ => None,
+            })
+            .collect()
+    } else {
+        HashSet::new()
+    };
+
+    resources
+}
+
+// -----------------------------------------------------------------------------
+/// Return `true` if a Factory in the town holds `resource`.
+fn has_factory_with(
+    board: &Board,
+    building_config: &BuildingConfig,
+    resource: Resource,
+) -> bool {
+    let has_factory = building_config.black() == BlackBuilding::Factory
+        && board.spaces().contains(&Space::BuildingWithOptResource(
+            BuildingType::Black,
+            Some(resource),
+        ));
+
+    has_factory
+}
+
+// -----------------------------------------------------------------------------
+/// Return the Factories and Banks in a town still waiting to be given a
+/// resource.
+pub fn awaiting_resource_idxs(
+    board: &Board,
+    building_config: &BuildingConfig,
+) -> Vec<usize> {
+    let idxs = match building_config.black() {
+        BlackBuilding::Bank | BlackBuilding::Factory => (0..board.elems())
+            .filter(|idx| {
+                board.spaces()[*idx]
+                    == Space::BuildingWithOptResource(BuildingType::Black, None)
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    idxs
+}
+
+// -----------------------------------------------------------------------------
+/// Return the resources on the Warehouse on space #idx.
+fn warehouse_resources(
+    board: &Board,
+    building_config: &BuildingConfig,
+    idx: usize,
+) -> Result<Vec<Resource>, GameError> {
+    if building_config.black() != BlackBuilding::Warehouse {
+        return Err(GameError::NotAWarehouse(idx));
+    }
+    let resources = match board.spaces().get(idx) {
+        None => return Err(GameError::IdxOutOfRange(idx)),
+        Some(Space::BuildingWithResources(BuildingType::Black, resources)) => {
+            resources.clone()
+        }
+        Some(_) => return Err(GameError::NotAWarehouse(idx)),
+    };
+
+    Ok(resources)
+}
+
+// -----------------------------------------------------------------------------
+/// Return `true` if some `Placement` is open to the town: it has an empty
+/// space, or a Warehouse with room.
+pub fn can_place(board: &Board, building_config: &BuildingConfig) -> bool {
+    let can_place = board.spaces().contains(&Space::Empty)
+        || (0..board.elems()).any(|idx| {
+            warehouse_resources(board, building_config, idx)
+                .is_ok_and(|resources| resources.len() < WAREHOUSE_CAPACITY)
+        });
+
+    can_place
+}
+
+// -----------------------------------------------------------------------------
+fn check_empty(board: &Board, idx: usize) -> Result<(), GameError> {
+    match board.spaces().get(idx) {
+        None => Err(GameError::IdxOutOfRange(idx)),
+        Some(Space::Empty) => Ok(()),
+        Some(_) => Err(GameError::SpaceNotEmpty(idx)),
+    }
+}
+
+// -----------------------------------------------------------------------------
+/// Put `resource` in a town according to `placement`.
+fn place(
+    board: &mut Board,
+    building_config: &BuildingConfig,
+    resource: Resource,
+    placement: Placement,
+) -> Result<(), GameError> {
+    match placement {
+        Placement::Space(idx) => {
+            check_empty(board, idx)?;
+            board.place(idx, resource);
+        }
+        Placement::Factory {
+            resource: replacement,
+            idx,
+        } => {
+            if !has_factory_with(board, building_config, resource) {
+                return Err(GameError::NoFactoryWith(resource));
+            }
+            check_empty(board, idx)?;
+            board.place(idx, replacement);
+        }
+        Placement::Warehouse(idx) => {
+            let mut resources =
+                warehouse_resources(board, building_config, idx)?;
+            if resources.len() >= WAREHOUSE_CAPACITY {
+                return Err(GameError::WarehouseFull(idx));
+            }
+            resources.push(resource);
+            board.store_resources(idx, resources);
+        }
+        Placement::WarehouseSwap {
+            warehouse,
+            resource: taken,
+            idx,
+        } => {
+            let mut resources =
+                warehouse_resources(board, building_config, warehouse)?;
+            let position = resources
+                .iter()
+                .position(|stored| *stored == taken)
+                .ok_or(GameError::NotInWarehouse {
+                    idx: warehouse,
+                    resource: taken,
+                })?;
+            check_empty(board, idx)?;
+            resources[position] = resource;
+            board.store_resources(warehouse, resources);
+            board.place(idx, taken);
+        }
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+impl Game {
+    /// Place this round's resource in a player's town, possibly using the
+    /// ability of a Factory or Warehouse.
+    pub fn place(
+        &mut self,
+        player: usize,
+        placement: Placement,
+    ) -> Result<(), GameError> {
+        let resource = self.named_resource.ok_or(GameError::NoResourceNamed)?;
+        let player_state = self
+            .players
+            .get_mut(player)
+            .ok_or(GameError::NoSuchPlayer(player))?;
+        if player_state.is_finished() {
+            return Err(GameError::PlayerFinished(player));
+        }
+        if player_state.has_placed {
+            return Err(GameError::AlreadyPlaced(player));
+        }
+        place(
+            &mut player_state.board,
+            &player_state.building_config,
+            resource,
+            placement,
+        )?;
+        player_state.has_placed = true;
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Put a resource on a newly constructed Factory or Bank.
+    pub fn assign_resource(
+        &mut self,
+        player: usize,
+        idx: usize,
+        resource: Resource,
+    ) -> Result<(), GameError> {
+        let player_state = self
+            .players
+            .get_mut(player)
+            .ok_or(GameError::NoSuchPlayer(player))?;
+        if !awaiting_resource_idxs(
+            &player_state.board,
+            &player_state.building_config,
+        )
+        .contains(&idx)
+        {
+            return Err(GameError::NotAwaitingResource(idx));
+        }
+        player_state.board.assign_resource(idx, resource);
+
+        Ok(())
+    }
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::pattern::PatternMatch;
+    use crate::building_config::{
+        BlueBuilding, GrayBuilding, GreenBuilding, MagentaBuilding,
+        OrangeBuilding, RedBuilding, YellowBuilding,
+    };
+
+    // -------------------------------------------------------------------------
+    fn new_game(black: BlackBuilding) -> Game {
+        let building_config = BuildingConfig::new(
+            black,
+            BlueBuilding::Cottage,
+            GrayBuilding::Well,
+            GreenBuilding::Tavern,
+            MagentaBuilding::SilvaForum,
+            OrangeBuilding::Chapel,
+            RedBuilding::Farm,
+            YellowBuilding::Tailor,
+        );
+
+        Game::new(building_config, 2, 4, 4)
+    }
+
+    // -------------------------------------------------------------------------
+    /// Place a black building on space #idx of player #0's town by hand.
+    fn build_black(game: &mut Game, idx: usize) {
+        let space =
+            game.building_config.new_building_space(BuildingType::Black);
+        game.players[0].board.place(idx, space);
+    }
+
+    // -------------------------------------------------------------------------
+    fn play_round(game: &mut Game, resource: Resource, placement: Placement) {
+        game.name_resource(resource).unwrap();
+        game.place(0, placement).unwrap();
+        game.place_resource(1, 15 - game.round() as usize).unwrap();
+        game.end_round().unwrap();
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_bank() {
+        let mut game = new_game(BlackBuilding::Bank);
+        build_black(&mut game, 0);
+        assert_eq!(
+            game.assign_resource(0, 1, Resource::Wood),
+            Err(GameError::NotAwaitingResource(1))
+        );
+
+        // A bank must be given a resource before the round can end.
+        game.name_resource(Resource::Wood).unwrap();
+        game.place_resource(0, 1).unwrap();
+        game.place_resource(1, 1).unwrap();
+        assert_eq!(game.end_round(), Err(GameError::RoundNotFinished));
+        game.assign_resource(0, 0, Resource::Glass).unwrap();
+        assert_eq!(
+            game.assign_resource(0, 0, Resource::Stone),
+            Err(GameError::NotAwaitingResource(0))
+        );
+        assert_eq!(
+            bank_resources(game.board(0).unwrap(), game.building_config()),
+            HashSet::from([Resource::Glass])
+        );
+        game.end_round().unwrap();
+
+        // Player #1 may name glass, but player #0 may not.
+        play_round(&mut game, Resource::Glass, Placement::Space(2));
+        assert_eq!(game.master_builder(), 0);
+        assert_eq!(
+            game.name_resource(Resource::Glass),
+            Err(GameError::ResourceOnBank(Resource::Glass))
+        );
+        game.name_resource(Resource::Wheat).unwrap();
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_factory() {
+        let mut game = new_game(BlackBuilding::Factory);
+        build_black(&mut game, 0);
+        game.assign_resource(0, 0, Resource::Brick).unwrap();
+
+        game.name_resource(Resource::Wood).unwrap();
+        assert_eq!(
+            game.place(
+                0,
+                Placement::Factory {
+                    resource: Resource::Glass,
+                    idx: 1
+                }
+            ),
+            Err(GameError::NoFactoryWith(Resource::Wood))
+        );
+        game.place_resource(0, 1).unwrap();
+        game.place_resource(1, 1).unwrap();
+        game.end_round().unwrap();
+
+        game.name_resource(Resource::Brick).unwrap();
+        game.place(
+            0,
+            Placement::Factory {
+                resource: Resource::Glass,
+                idx: 2,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            game.board(0).unwrap().spaces()[2],
+            Space::Resource(Resource::Glass)
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_warehouse() {
+        let mut game = new_game(BlackBuilding::Warehouse);
+        build_black(&mut game, 0);
+        let warehouse =
+            |game: &Game| game.board(0).unwrap().spaces()[0].clone();
+
+        play_round(&mut game, Resource::Wood, Placement::Warehouse(0));
+        play_round(&mut game, Resource::Wood, Placement::Warehouse(0));
+        play_round(&mut game, Resource::Stone, Placement::Warehouse(0));
+        assert_eq!(
+            warehouse(&game),
+            Space::BuildingWithResources(
+                BuildingType::Black,
+                vec![Resource::Wood, Resource::Wood, Resource::Stone]
+            )
+        );
+
+        game.name_resource(Resource::Glass).unwrap();
+        assert_eq!(
+            game.place(0, Placement::Warehouse(0)),
+            Err(GameError::WarehouseFull(0))
+        );
+        assert_eq!(
+            game.place(0, Placement::Warehouse(1)),
+            Err(GameError::NotAWarehouse(1))
+        );
+        assert_eq!(
+            game.place(
+                0,
+                Placement::WarehouseSwap {
+                    warehouse: 0,
+                    resource: Resource::Brick,
+                    idx: 1
+                }
+            ),
+            Err(GameError::NotInWarehouse {
+                idx: 0,
+                resource: Resource::Brick
+            })
+        );
+        game.place(
+            0,
+            Placement::WarehouseSwap {
+                warehouse: 0,
+                resource: Resource::Stone,
+                idx: 1,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            warehouse(&game),
+            Space::BuildingWithResources(
+                BuildingType::Black,
+                vec![Resource::Wood, Resource::Wood, Resource::Glass]
+            )
+        );
+        assert_eq!(
+            game.board(0).unwrap().spaces()[1],
+            Space::Resource(Resource::Stone)
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_warehouse_in_full_town() {
+        let mut game = new_game(BlackBuilding::Warehouse);
+        build_black(&mut game, 0);
+        for idx in 1..16 {
+            game.players[0].board.place(idx, Resource::Stone);
+        }
+
+        // With no empty space left, the warehouse still takes the resource,
+        // and the town is complete only once it is full.
+        for round in 1..=WAREHOUSE_CAPACITY {
+            assert!(!game.players()[0].is_finished());
+            game.name_resource(Resource::Wood).unwrap();
+            assert!(!game.players()[0].has_placed());
+            game.place(0, Placement::Warehouse(0)).unwrap();
+            game.place_resource(1, 15 - round).unwrap();
+            game.end_round().unwrap();
+        }
+        assert!(game.players()[0].is_finished());
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_trading_post() {
+        let mut game = new_game(BlackBuilding::TradingPost);
+        build_black(&mut game, 0);
+        play_round(&mut game, Resource::Stone, Placement::Space(1));
+
+        let well = PatternMatch::new(BuildingType::Gray, [0, 1].into());
+        game.construct(0, &well, 1, BuildingType::Gray).unwrap();
+        let board = game.board(0).unwrap();
+        assert_eq!(board.spaces()[0], Space::Building(BuildingType::Black));
+        assert_eq!(board.spaces()[1], Space::Building(BuildingType::Gray));
+    }
+}
@@ -0,0 +1,1000 @@
+use std::error;
+use std::fmt;
+
+use crate::board::construct::ConstructError;
+use crate::board::pattern::PatternMatch;
+use crate::board::space::{BuildingType, Resource};
+use crate::board::Board;
+use crate::building_config::{BuildingConfig, MagentaBuilding};
+use crate::game::agent::Agent;
+use crate::game::black::{
+    awaiting_resource_idxs, bank_resources, can_place, Placement,
+};
+use crate::game::monument::{can_build_anywhere, PendingEffect};
+use crate::score::standings::{rank, Standings};
+use crate::score::{score_players, ScoreCard, ScoringContext};
+
+pub mod agent;
+pub mod black;
+pub mod monument;
+pub mod setup;
+pub mod solo;
+
+// =============================================================================
+/// Reasons an action may be refused by the game.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GameError {
+    /// The player does not exist.
+    NoSuchPlayer(usize),
+    /// The number of agents does not match the number of players.
+    WrongNumberOfAgents { agents: usize, players: usize },
+    /// The player's town is complete and they are out of the game.
+    PlayerFinished(usize),
+    /// Every player's town is complete.
+    GameOver,
+    /// The master builder has already named a resource this round.
+    ResourceAlreadyNamed,
+    /// The master builder has not named a resource yet this round.
+    NoResourceNamed,
+    /// The player has already placed this round's resource.
+    AlreadyPlaced(usize),
+    /// Some players have not placed this round's resource yet.
+    RoundNotFinished,
+    /// The space does not exist on the board.
+    IdxOutOfRange(usize),
+    /// The space is already occupied.
+    SpaceNotEmpty(usize),
+    /// The construction was refused by the board.
+    Construct(ConstructError),
+    /// The master builder cannot name the resource on their Bank.
+    ResourceOnBank(Resource),
+    /// The space is not a Factory or Bank waiting for a resource.
+    NotAwaitingResource(usize),
+    /// No Factory in the town holds the named resource.
+    NoFactoryWith(Resource),
+    /// The space is not a Warehouse.
+    NotAWarehouse(usize),
+    /// The Warehouse is already holding as many resources as it can.
+    WarehouseFull(usize),
+    /// The Warehouse does not hold the resource.
+    NotInWarehouse { idx: usize, resource: Resource },
+    /// Monuments can only be constructed from their pattern.
+    MonumentNotAllowed,
+    /// The player has already built their monument.
+    MonumentAlreadyBuilt(usize),
+    /// The monument was not dealt to the player.
+    MonumentNotInHand(MagentaBuilding),
+    /// The player has not chosen a monument from their hand yet.
+    MonumentNotChosen(usize),
+    /// There are not enough monuments to deal a hand to this many players.
+    TooManyPlayers(usize),
+    /// The space does not hold a building.
+    NotABuilding(usize),
+    /// A building cannot be replaced with one of the same type.
+    SameBuildingType(BuildingType),
+    /// The player has no effect waiting to be resolved.
+    NoPendingEffect(usize),
+    /// The resolution does not fit the player's oldest pending effect.
+    WrongResolution(PendingEffect),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::NoSuchPlayer(player) => {
+                write!(f, "there is no player #{player}")
+            }
+            GameError::WrongNumberOfAgents { agents, players } => {
+                write!(f, "{agents} agents cannot play for {players} players")
+            }
+            GameError::PlayerFinished(player) => {
+                write!(f, "player #{player} has finished their town")
+            }
+            GameError::GameOver => write!(f, "the game is over"),
+            GameError::ResourceAlreadyNamed => {
+                write!(f, "a resource has already been named this round")
+            }
+            GameError::NoResourceNamed => {
+                write!(f, "no resource has been named this round")
+            }
+            GameError::AlreadyPlaced(player) => {
+                write!(f, "player #{player} has already placed a resource")
+            }
+            GameError::RoundNotFinished => {
+                write!(f, "not every player has placed a resource")
+            }
+            GameError::IdxOutOfRange(idx) => {
+                write!(f, "space #{idx} is not on the board")
+            }
+            GameError::SpaceNotEmpty(idx) => {
+                write!(f, "space #{idx} is not empty")
+            }
+            GameError::Construct(err) => err.fmt(f),
+            GameError::ResourceOnBank(resource) => {
+                write!(f, "{resource:?} is on the master builder's bank")
+            }
+            GameError::NotAwaitingResource(idx) => {
+                write!(f, "space #{idx} is not waiting for a resource")
+            }
+            GameError::NoFactoryWith(resource) => {
+                write!(f, "no factory holds {resource:?}")
+            }
+            GameError::NotAWarehouse(idx) => {
+                write!(f, "space #{idx} is not a warehouse")
+            }
+            GameError::WarehouseFull(idx) => {
+                write!(f, "the warehouse on space #{idx} is full")
+            }
+            GameError::NotInWarehouse { idx, resource } => {
+                write!(f, "the warehouse on space #{idx} holds no {resource:?}")
+            }
+            GameError::MonumentNotAllowed => {
+                write!(f, "monuments can only be constructed from a pattern")
+            }
+            GameError::MonumentAlreadyBuilt(player) => {
+                write!(f, "player #{player} has already built their monument")
+            }
+            GameError::MonumentNotInHand(monument) => {
+                write!(f, "{monument:?} was not dealt to the player")
+            }
+            GameError::MonumentNotChosen(player) => {
+                write!(f, "player #{player} has not chosen a monument")
+            }
+            GameError::TooManyPlayers(num_players) => {
+                write!(
+                    f,
+                    "not enough monuments to deal to {num_players} players"
+                )
+            }
+            GameError::NotABuilding(idx) => {
+                write!(f, "space #{idx} does not hold a building")
+            }
+            GameError::SameBuildingType(building_type) => {
+                write!(f, "the building is already a {building_type:?} one")
+            }
+            GameError::NoPendingEffect(player) => {
+                write!(f, "player #{player} has no effect to resolve")
+            }
+            GameError::WrongResolution(effect) => {
+                write!(f, "the resolution does not fit {effect:?}")
+            }
+        }
+    }
+}
+
+impl error::Error for GameError {}
+
+impl From<ConstructError> for GameError {
+    fn from(err: ConstructError) -> Self {
+        GameError::Construct(err)
+    }
+}
+
+// =============================================================================
+/// A player's town and their progress through the current round.
+pub struct Player {
+    board: Board,
+    building_config: BuildingConfig,
+    has_placed: bool,
+    finished_round: Option<u32>,
+    pending_effects: Vec<PendingEffect>,
+    watched_buildings: Vec<BuildingType>,
+    monument_hand: Vec<MagentaBuilding>,
+}
+
+impl Player {
+    fn new(rows: usize, cols: usize, building_config: BuildingConfig) -> Self {
+        Self {
+            board: Board::new(rows, cols),
+            building_config,
+            has_placed: false,
+            finished_round: None,
+            pending_effects: Vec::new(),
+            watched_buildings: Vec::new(),
+            monument_hand: Vec::new(),
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the cards the player's town is built and scored with: the
+    /// shared buildings and the player's own monument.
+    pub fn building_config(&self) -> &BuildingConfig {
+        &self.building_config
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn monument(&self) -> MagentaBuilding {
+        self.building_config.magenta()
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the monuments the player was dealt and has yet to choose from.
+    pub fn monument_hand(&self) -> &Vec<MagentaBuilding> {
+        &self.monument_hand
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn has_placed(&self) -> bool {
+        self.has_placed
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the round in which the player's town was completed, if it has
+    /// been.
+    pub fn finished_round(&self) -> Option<u32> {
+        self.finished_round
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn is_finished(&self) -> bool {
+        self.finished_round.is_some()
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the effects waiting to be resolved, oldest first.
+    pub fn pending_effects(&self) -> &Vec<PendingEffect> {
+        &self.pending_effects
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the buildings held on the player's Opaleye's Watch.
+    pub fn watched_buildings(&self) -> &Vec<BuildingType> {
+        &self.watched_buildings
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return `true` if the player still has to place this round's resource.
+    fn must_place(&self) -> bool {
+        !self.is_finished() && !self.has_placed
+    }
+}
+
+// =============================================================================
+/// A multiplayer game. Each round the master builder names a resource, every
+/// player places it in their town and may construct buildings, and then the
+/// role of master builder passes to the next player.
+pub struct Game {
+    building_config: BuildingConfig,
+    players: Vec<Player>,
+    master_builder: usize,
+    round: u32,
+    named_resource: Option<Resource>,
+    finish_order: Vec<usize>,
+}
+
+impl Game {
+    pub fn new(
+        building_config: BuildingConfig,
+        num_players: usize,
+        rows: usize,
+        cols: usize,
+    ) -> Self {
+        assert!(num_players > 0);
+        let players = (0..num_players)
+            .map(|_| Player::new(rows, cols, building_config.clone()))
+            .collect();
+        Self {
+            building_config,
+            players,
+            master_builder: 0,
+            round: 1,
+            named_resource: None,
+            finish_order: Vec::new(),
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the buildings shared by every player. Each player's monument is
+    /// in `Player::building_config`.
+    pub fn building_config(&self) -> &BuildingConfig {
+        &self.building_config
+    }
+
+    // -------------------------------------------------------------------------
+    /// Give a player their own monument in place of the shared one. The
+    /// monument cannot change once it has been built.
+    pub fn set_monument(
+        &mut self,
+        player: usize,
+        monument: MagentaBuilding,
+    ) -> Result<(), GameError> {
+        let player_state = self
+            .players
+            .get_mut(player)
+            .ok_or(GameError::NoSuchPlayer(player))?;
+        if player_state
+            .board
+            .count_building_type(BuildingType::Magenta)
+            > 0
+        {
+            return Err(GameError::MonumentAlreadyBuilt(player));
+        }
+        player_state.building_config =
+            self.building_config.with_magenta(monument);
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn players(&self) -> &Vec<Player> {
+        &self.players
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn num_players(&self) -> usize {
+        self.players.len()
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn board(&self, player: usize) -> Result<&Board, GameError> {
+        let board = self
+            .players
+            .get(player)
+            .map(|player| player.board())
+            .ok_or(GameError::NoSuchPlayer(player))?;
+
+        Ok(board)
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn master_builder(&self) -> usize {
+        self.master_builder
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn named_resource(&self) -> Option<Resource> {
+        self.named_resource
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return `true` once every player's town is complete.
+    pub fn is_over(&self) -> bool {
+        self.players.iter().all(|player| player.is_finished())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the players whose towns are complete, in the order they
+    /// finished. Players finishing in the same round are listed in turn order,
+    /// starting with that round's master builder.
+    pub fn finish_order(&self) -> &Vec<usize> {
+        &self.finish_order
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the 1-based position in which a player finished. Players that
+    /// finished in the same round share a position.
+    pub fn finish_position(&self, player: usize) -> Option<usize> {
+        let finished_round = self.players.get(player)?.finished_round?;
+        let num_finished_earlier = self
+            .players
+            .iter()
+            .filter(|other| {
+                other
+                    .finished_round
+                    .is_some_and(|round| round < finished_round)
+            })
+            .count();
+
+        Some(num_finished_earlier + 1)
+    }
+
+    // -------------------------------------------------------------------------
+    /// Name the resource every player must place this round.
+    pub fn name_resource(
+        &mut self,
+        resource: Resource,
+    ) -> Result<(), GameError> {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        if self.named_resource.is_some() {
+            return Err(GameError::ResourceAlreadyNamed);
+        }
+        if let Some(player) = (0..self.players.len())
+            .find(|player| !self.players[*player].monument_hand.is_empty())
+        {
+            return Err(GameError::MonumentNotChosen(player));
+        }
+        // In a solo game the resource is drawn rather than named, so banks
+        // have no effect.
+        let master_builder = &self.players[self.master_builder];
+        if self.players.len() > 1
+            && bank_resources(
+                master_builder.board(),
+                master_builder.building_config(),
+            )
+            .contains(&resource)
+        {
+            return Err(GameError::ResourceOnBank(resource));
+        }
+        self.named_resource = Some(resource);
+
+        // Players with nowhere to put the resource pass on placing it, but may
+        // still construct this round.
+        for player in self.players.iter_mut() {
+            if !can_place(&player.board, &player.building_config) {
+                player.has_placed = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Place this round's resource on an empty space in a player's town.
+    pub fn place_resource(
+        &mut self,
+        player: usize,
+        idx: usize,
+    ) -> Result<(), GameError> {
+        self.place(player, Placement::Space(idx))
+    }
+
+    // -------------------------------------------------------------------------
+    /// Construct a building in a player's town from a matched pattern, and
+    /// queue the effects it triggers.
+    pub fn construct(
+        &mut self,
+        player: usize,
+        pattern_match: &PatternMatch,
+        idx: usize,
+        building_type: BuildingType,
+    ) -> Result<(), GameError> {
+        let player_state = self
+            .players
+            .get_mut(player)
+            .ok_or(GameError::NoSuchPlayer(player))?;
+        if player_state.is_finished() {
+            return Err(GameError::PlayerFinished(player));
+        }
+        let building_config = &player_state.building_config;
+        if can_build_anywhere(&player_state.board, building_config) {
+            player_state.board.construct_anywhere(
+                building_config,
+                pattern_match,
+                idx,
+                building_type,
+            )?;
+        } else {
+            player_state.board.construct(
+                building_config,
+                pattern_match,
+                idx,
+                building_type,
+            )?;
+        }
+        self.trigger_effects(player, building_type);
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Finish the round once every player has placed the named resource, every
+    /// new Factory or Bank has been given a resource and no effects are
+    /// pending, then pass the role of master builder to the next player who is
+    /// still in the game. Players who can neither place a resource nor
+    /// construct anything are out of the game from this round on.
+    pub fn end_round(&mut self) -> Result<(), GameError> {
+        if self.named_resource.is_none() {
+            return Err(GameError::NoResourceNamed);
+        }
+        if self.players.iter().any(|player| {
+            player.must_place()
+                || !player.pending_effects.is_empty()
+                || !awaiting_resource_idxs(
+                    player.board(),
+                    player.building_config(),
+                )
+                .is_empty()
+        }) {
+            return Err(GameError::RoundNotFinished);
+        }
+
+        let num_players = self.players.len();
+        for turn in 0..num_players {
+            let player = (self.master_builder + turn) % num_players;
+            let player_state = &mut self.players[player];
+            player_state.has_placed = false;
+            if !player_state.is_finished()
+                && player_state
+                    .board
+                    .is_complete(&player_state.building_config)
+                && !can_place(
+                    &player_state.board,
+                    &player_state.building_config,
+                )
+            {
+                player_state.finished_round = Some(self.round);
+                self.finish_order.push(player);
+            }
+        }
+        for player in 0..self.players.len() {
+            let finish_position = self.finish_position(player);
+            self.players[player]
+                .board
+                .set_finish_position(finish_position);
+        }
+        self.named_resource = None;
+        self.round += 1;
+
+        if let Some(next) = (1..=num_players)
+            .map(|turn| (self.master_builder + turn) % num_players)
+            .find(|player| !self.players[*player].is_finished())
+        {
+            self.master_builder = next;
+        }
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Check that there is one agent for each player.
+    pub(crate) fn check_agents(
+        &self,
+        agents: &[Box<dyn Agent>],
+    ) -> Result<(), GameError> {
+        if agents.len() != self.players.len() {
+            return Err(GameError::WrongNumberOfAgents {
+                agents: agents.len(),
+                players: self.players.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Play one full round, asking each player's agent for their decisions.
+    /// Players act in turn order, starting with the master builder. Players
+    /// who are out of the game are skipped.
+    pub fn play_round(
+        &mut self,
+        agents: &mut [Box<dyn Agent>],
+    ) -> Result<(), GameError> {
+        self.check_agents(agents)?;
+        let master_builder = self.master_builder;
+
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        let resource =
+            agents[master_builder].name_resource(self, master_builder);
+        self.play_round_with(agents, resource)
+    }
+
+    // -------------------------------------------------------------------------
+    /// Play one full round with `resource` as the named resource, asking each
+    /// player's agent for the rest of their decisions.
+    pub(crate) fn play_round_with(
+        &mut self,
+        agents: &mut [Box<dyn Agent>],
+        resource: Resource,
+    ) -> Result<(), GameError> {
+        self.check_agents(agents)?;
+        let num_players = self.players.len();
+        let master_builder = self.master_builder;
+        self.name_resource(resource)?;
+
+        for turn in 0..num_players {
+            let player = (master_builder + turn) % num_players;
+            if self.players[player].is_finished() {
+                continue;
+            }
+            if self.players[player].must_place() {
+                let placement =
+                    agents[player].place_resource(self, player, resource);
+                self.place(player, placement)?;
+            }
+            while let Some((pattern_match, idx)) =
+                agents[player].construct(self, player)
+            {
+                let building_type = pattern_match.building_type();
+                self.construct(player, &pattern_match, idx, building_type)?;
+                self.resolve_with_agent(agents, player)?;
+            }
+        }
+
+        // Effects triggered by other players' constructions.
+        for turn in 0..num_players {
+            let player = (master_builder + turn) % num_players;
+            self.resolve_with_agent(agents, player)?;
+        }
+
+        self.end_round()
+    }
+
+    // -------------------------------------------------------------------------
+    /// Ask a player's agent to resolve their pending effects and give a
+    /// resource to any new Factory or Bank.
+    fn resolve_with_agent(
+        &mut self,
+        agents: &mut [Box<dyn Agent>],
+        player: usize,
+    ) -> Result<(), GameError> {
+        loop {
+            if let Some(idx) = awaiting_resource_idxs(
+                self.players[player].board(),
+                self.players[player].building_config(),
+            )
+            .first()
+            {
+                let resource =
+                    agents[player].assign_resource(self, player, *idx);
+                self.assign_resource(player, *idx, resource)?;
+            } else if let Some(effect) =
+                self.players[player].pending_effects.first()
+            {
+                let resolution =
+                    agents[player].resolve_effect(self, player, *effect);
+                self.resolve_effect(player, resolution)?;
+            } else {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Play rounds until every player's town is complete.
+    pub fn play(
+        &mut self,
+        agents: &mut [Box<dyn Agent>],
+    ) -> Result<(), GameError> {
+        while !self.is_over() {
+            self.play_round(agents)?;
+        }
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Score every player's town. Feast Halls are compared against the town
+    /// of the player to the right.
+    pub fn score(&self, scoring_context: &ScoringContext) -> Vec<ScoreCard> {
+        let boards: Vec<&Board> =
+            self.players.iter().map(|player| player.board()).collect();
+        let building_configs: Vec<&BuildingConfig> = self
+            .players
+            .iter()
+            .map(|player| player.building_config())
+            .collect();
+        let score_cards =
+            score_players(&boards, &building_configs, scoring_context);
+
+        score_cards
+    }
+
+    // -------------------------------------------------------------------------
+    /// Score every player's town and rank the players.
+    pub fn standings(&self, scoring_context: &ScoringContext) -> Standings {
+        let boards: Vec<&Board> =
+            self.players.iter().map(|player| player.board()).collect();
+        let standings = rank(&boards, &self.score(scoring_context));
+
+        standings
+    }
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::space::Space;
+    use crate::building_config::{
+        BlackBuilding, BlueBuilding, GrayBuilding, GreenBuilding,
+        MagentaBuilding, OrangeBuilding, RedBuilding, YellowBuilding,
+    };
+    use crate::game::monument::Resolution;
+
+    // -------------------------------------------------------------------------
+    fn new_game(num_players: usize) -> Game {
+        let building_config = BuildingConfig::new(
+            BlackBuilding::Warehouse,
+            BlueBuilding::Cottage,
+            GrayBuilding::Shed,
+            GreenBuilding::Tavern,
+            MagentaBuilding::SilvaForum,
+            OrangeBuilding::Chapel,
+            RedBuilding::Farm,
+            YellowBuilding::Tailor,
+        );
+
+        Game::new(building_config, num_players, 4, 4)
+    }
+
+    // -------------------------------------------------------------------------
+    /// Names resources from a repeating script, places them in reading order
+    /// and constructs the first building it can.
+    struct ScriptedAgent {
+        resources: Vec<Resource>,
+    }
+
+    impl Agent for ScriptedAgent {
+        fn name_resource(&mut self, _game: &Game, _player: usize) -> Resource {
+            self.resources.rotate_left(1);
+            *self.resources.last().unwrap()
+        }
+
+        fn place_resource(
+            &mut self,
+            game: &Game,
+            player: usize,
+            _resource: Resource,
+        ) -> Placement {
+            let board = game.board(player).unwrap();
+            let idx = (0..board.elems())
+                .find(|idx| board.spaces()[*idx] == Space::Empty)
+                .unwrap();
+            Placement::Space(idx)
+        }
+
+        fn assign_resource(
+            &mut self,
+            _game: &Game,
+            _player: usize,
+            _idx: usize,
+        ) -> Resource {
+            self.resources[0]
+        }
+
+        fn resolve_effect(
+            &mut self,
+            _game: &Game,
+            _player: usize,
+            _effect: PendingEffect,
+        ) -> Resolution {
+            Resolution::Skip
+        }
+
+        fn keep_monument(
+            &mut self,
+            _game: &Game,
+            _player: usize,
+            hand: &[MagentaBuilding],
+        ) -> MagentaBuilding {
+            hand[0]
+        }
+
+        fn construct(
+            &mut self,
+            game: &Game,
+            player: usize,
+        ) -> Option<(PatternMatch, usize)> {
+            let board = game.board(player).unwrap();
+            let pattern_match = board
+                .matching_patterns(game.players()[player].building_config())
+                .into_iter()
+                .next()?;
+            let idx = *pattern_match.idxs().iter().min().unwrap();
+            Some((pattern_match, idx))
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_round() {
+        let mut game = new_game(2);
+        assert_eq!(game.master_builder(), 0);
+        assert_eq!(game.round(), 1);
+
+        // Nothing can be placed until a resource is named.
+        assert_eq!(game.place_resource(0, 0), Err(GameError::NoResourceNamed));
+        assert_eq!(game.end_round(), Err(GameError::NoResourceNamed));
+
+        game.name_resource(Resource::Wood).unwrap();
+        assert_eq!(
+            game.name_resource(Resource::Stone),
+            Err(GameError::ResourceAlreadyNamed)
+        );
+
+        game.place_resource(0, 0).unwrap();
+        assert_eq!(game.place_resource(0, 1), Err(GameError::AlreadyPlaced(0)));
+        assert_eq!(game.end_round(), Err(GameError::RoundNotFinished));
+        assert_eq!(
+            game.place_resource(1, 16),
+            Err(GameError::IdxOutOfRange(16))
+        );
+        assert_eq!(game.place_resource(2, 0), Err(GameError::NoSuchPlayer(2)));
+        game.place_resource(1, 5).unwrap();
+
+        game.end_round().unwrap();
+        assert_eq!(game.master_builder(), 1);
+        assert_eq!(game.round(), 2);
+        assert_eq!(game.named_resource(), None);
+
+        // Spaces must be empty.
+        game.name_resource(Resource::Stone).unwrap();
+        assert_eq!(game.place_resource(0, 0), Err(GameError::SpaceNotEmpty(0)));
+        game.place_resource(0, 1).unwrap();
+        game.place_resource(1, 6).unwrap();
+
+        // Player #0 has a well, player #1 does not.
+        let well = PatternMatch::new(BuildingType::Gray, [0, 1].into());
+        game.construct(0, &well, 0, BuildingType::Gray).unwrap();
+        assert_eq!(
+            game.construct(1, &well, 0, BuildingType::Gray),
+            Err(GameError::Construct(ConstructError::PatternNotOnBoard))
+        );
+        game.end_round().unwrap();
+        assert_eq!(game.master_builder(), 0);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_play_round() {
+        let mut game = new_game(2);
+        let mut agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(ScriptedAgent {
+                resources: vec![Resource::Wood],
+            }),
+            Box::new(ScriptedAgent {
+                resources: vec![Resource::Stone],
+            }),
+        ];
+        assert_eq!(
+            game.play_round(&mut agents[..1]),
+            Err(GameError::WrongNumberOfAgents {
+                agents: 1,
+                players: 2
+            })
+        );
+        game.play_round(&mut agents).unwrap();
+        game.play_round(&mut agents).unwrap();
+        assert_eq!(game.round(), 3);
+
+        // Both players built a shed from the wood and the stone.
+        for player in 0..game.num_players() {
+            let board = game.board(player).unwrap();
+            assert_eq!(board.spaces()[0], Space::Building(BuildingType::Gray));
+            assert_eq!(board.spaces()[1], Space::Empty);
+        }
+
+        let scoring_context = ScoringContext::default();
+        let score_cards = game.score(&scoring_context);
+        assert_eq!(score_cards.len(), 2);
+        assert!(score_cards.iter().all(|card| card.score_gray() == 1));
+        assert!(score_cards.iter().all(|card| card.score_unused() == -15));
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_elimination() {
+        let mut game = new_game(3);
+        // Player #1 only has one empty space left.
+        for idx in 1..16 {
+            game.players[1].board.place(idx, Resource::Stone);
+        }
+
+        game.name_resource(Resource::Stone).unwrap();
+        game.place_resource(0, 0).unwrap();
+        game.place_resource(1, 0).unwrap();
+        game.place_resource(2, 0).unwrap();
+        game.end_round().unwrap();
+        assert_eq!(game.finish_order(), &vec![1]);
+        assert_eq!(game.finish_position(1), Some(1));
+        assert_eq!(game.finish_position(0), None);
+        assert_eq!(game.board(1).unwrap().finish_position(), Some(1));
+        assert_eq!(game.board(0).unwrap().finish_position(), None);
+        assert!(!game.is_over());
+
+        // Player #1 is skipped as master builder and can no longer play.
+        assert_eq!(game.master_builder(), 2);
+        game.name_resource(Resource::Wood).unwrap();
+        assert_eq!(
+            game.place_resource(1, 0),
+            Err(GameError::PlayerFinished(1))
+        );
+
+        // Player #2 fills their town, but a well can still be built, so they
+        // stay in the game and may construct without placing.
+        for idx in 2..16 {
+            game.players[2].board.place(idx, Resource::Stone);
+        }
+        game.place_resource(2, 1).unwrap();
+        game.place_resource(0, 1).unwrap();
+        game.end_round().unwrap();
+        assert!(!game.players[2].is_finished());
+        assert_eq!(game.master_builder(), 0);
+
+        game.name_resource(Resource::Stone).unwrap();
+        game.place_resource(0, 2).unwrap();
+        assert_eq!(game.place_resource(2, 2), Err(GameError::AlreadyPlaced(2)));
+        let well = PatternMatch::new(BuildingType::Gray, [1, 2].into());
+        game.construct(2, &well, 1, BuildingType::Gray).unwrap();
+        game.end_round().unwrap();
+        assert!(!game.players[2].is_finished());
+
+        // With a space emptied by the well, player #2 places one last stone.
+        game.name_resource(Resource::Stone).unwrap();
+        game.place_resource(0, 3).unwrap();
+        game.place_resource(2, 2).unwrap();
+        game.end_round().unwrap();
+        assert_eq!(game.finish_order(), &vec![1, 2]);
+        assert_eq!(game.finish_position(2), Some(2));
+        assert_eq!(game.master_builder(), 0);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_finish_order() {
+        // Players #0 and #2 finish in the same round, with player #2 as
+        // master builder, so player #2 is listed first.
+        let mut game = new_game(3);
+        game.master_builder = 2;
+        for player in [0, 2] {
+            for idx in 1..16 {
+                game.players[player].board.place(idx, Resource::Stone);
+            }
+        }
+
+        game.name_resource(Resource::Stone).unwrap();
+        for player in 0..3 {
+            game.place_resource(player, 0).unwrap();
+        }
+        game.end_round().unwrap();
+        assert_eq!(game.finish_order(), &vec![2, 0]);
+        assert_eq!(game.finish_position(0), Some(1));
+        assert_eq!(game.finish_position(2), Some(1));
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_play() {
+        let mut game = new_game(2);
+        let mut agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(ScriptedAgent {
+                resources: vec![Resource::Stone],
+            }),
+            Box::new(ScriptedAgent {
+                resources: vec![Resource::Brick],
+            }),
+        ];
+        game.play(&mut agents).unwrap();
+        assert!(game.is_over());
+        assert_eq!(game.round(), 17);
+        assert_eq!(game.finish_position(0), Some(1));
+        assert_eq!(game.finish_position(1), Some(1));
+        assert_eq!(game.play_round(&mut agents), Err(GameError::GameOver));
+
+        // Both towns are full of stone and brick respectively.
+        let standings = game.standings(&ScoringContext::default());
+        assert_eq!(standings.winners(), vec![0, 1]);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_set_monument() {
+        let mut game = new_game(2);
+        game.set_monument(1, MagentaBuilding::FortIronweed).unwrap();
+        assert_eq!(game.players()[0].monument(), MagentaBuilding::SilvaForum);
+        assert_eq!(game.players()[1].monument(), MagentaBuilding::FortIronweed);
+        assert_eq!(
+            game.set_monument(2, MagentaBuilding::FortIronweed),
+            Err(GameError::NoSuchPlayer(2))
+        );
+
+        // Each town is scored with its own monument.
+        for player in 0..2 {
+            game.players[player].board.place(0, BuildingType::Magenta);
+        }
+        let score_cards = game.score(&ScoringContext::default());
+        assert_eq!(score_cards[0].score_magenta(), 2);
+        assert_eq!(score_cards[1].score_magenta(), 7);
+
+        assert_eq!(
+            game.set_monument(1, MagentaBuilding::SilvaForum),
+            Err(GameError::MonumentAlreadyBuilt(1))
+        );
+    }
+}
@@ -0,0 +1,465 @@
+use crate::board::space::{BuildingType, Space};
+use crate::board::Board;
+use crate::building_config::{BuildingConfig, MagentaBuilding};
+use crate::game::{Game, GameError};
+
+/// The number of buildings an Architect's Guild may replace.
+pub const ARCHITECTS_GUILD_REPLACEMENTS: u32 = 2;
+
+/// The number of different buildings held on an Opaleye's Watch.
+pub const OPALEYES_WATCH_BUILDINGS: usize = 3;
+
+// =============================================================================
+/// An effect waiting to be resolved by the player it belongs to.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PendingEffect {
+    /// Architect's Guild: replace up to `remaining` more buildings with
+    /// buildings of another type.
+    ReplaceBuildings { remaining: u32 },
+    /// Grove University: place a building on an empty space.
+    PlaceBuilding,
+    /// Opaleye's Watch: choose the different buildings to hold.
+    WatchBuildings,
+    /// Opaleye's Watch: another player constructed a held building, which must
+    /// now be placed on an empty space.
+    PlaceWatchedBuilding(BuildingType),
+}
+
+impl PendingEffect {
+    /// Return `true` if the player may decline the effect at any time. Other
+    /// effects may only be declined when there is no empty space to use them.
+    fn is_optional(&self) -> bool {
+        matches!(self, PendingEffect::ReplaceBuildings { .. })
+    }
+}
+
+// =============================================================================
+/// A player's choice for resolving their oldest pending effect.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Resolution {
+    /// Replace the building on space #idx with a `building_type` building.
+    Replace {
+        idx: usize,
+        building_type: BuildingType,
+    },
+    /// Place a `building_type` building on empty space #idx.
+    Place {
+        idx: usize,
+        building_type: BuildingType,
+    },
+    /// Hold these buildings on an Opaleye's Watch.
+    Watch(Vec<BuildingType>),
+    /// Decline the effect.
+    Skip,
+}
+
+// -----------------------------------------------------------------------------
+/// Return the effects triggered by constructing `monument`.
+pub fn on_build_effects(monument: MagentaBuilding) -> Vec<PendingEffect> {
+    let effects = match monument {
+        MagentaBuilding::ArchitectsGuild => {
+            vec![PendingEffect::ReplaceBuildings {
+                remaining: ARCHITECTS_GUILD_REPLACEMENTS,
+            }]
+        }
+        MagentaBuilding::GroveUniversity => vec![PendingEffect::PlaceBuilding],
+        MagentaBuilding::OpaleyesWatch => vec![PendingEffect::WatchBuildings],
+        _ => Vec::new(),
+    };
+
+    effects
+}
+
+// -----------------------------------------------------------------------------
+/// Return `true` if buildings in the town may be constructed on any empty
+/// space, thanks to an Obelisk of the Crescent.
+pub fn can_build_anywhere(
+    board: &Board,
+    building_config: &BuildingConfig,
+) -> bool {
+    let can_build_anywhere = building_config.magenta()
+        == MagentaBuilding::ObeliskOfTheCrescent
+        && board.count_building_type(BuildingType::Magenta) > 0;
+
+    can_build_anywhere
+}
+
+// -----------------------------------------------------------------------------
+/// Place a new `building_type` building on empty space #idx. Monuments may
+/// only be constructed from their pattern.
+fn place_building(
+    board: &mut Board,
+    building_config: &BuildingConfig,
+    idx: usize,
+    building_type: BuildingType,
+) -> Result<(), GameError> {
+    if building_type == BuildingType::Magenta {
+        return Err(GameError::MonumentNotAllowed);
+    }
+    match board.spaces().get(idx) {
+        None => return Err(GameError::IdxOutOfRange(idx)),
+        Some(Space::Empty) => (),
+        Some(_) => return Err(GameError::SpaceNotEmpty(idx)),
+    }
+    board.place(idx, building_config.new_building_space(building_type));
+    board.record_buildings_when_built(idx);
+
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+/// Replace the building on space #idx with a `building_type` building.
+/// Monuments may be neither replaced nor used as a replacement.
+fn replace_building(
+    board: &mut Board,
+    building_config: &BuildingConfig,
+    idx: usize,
+    building_type: BuildingType,
+) -> Result<(), GameError> {
+    if building_type == BuildingType::Magenta {
+        return Err(GameError::MonumentNotAllowed);
+    }
+    let old_building_type = board
+        .spaces()
+        .get(idx)
+        .ok_or(GameError::IdxOutOfRange(idx))?
+        .building_type()
+        .ok_or(GameError::NotABuilding(idx))?;
+    if old_building_type == BuildingType::Magenta {
+        return Err(GameError::MonumentNotAllowed);
+    }
+    if old_building_type == building_type {
+        return Err(GameError::SameBuildingType(building_type));
+    }
+    board.place(idx, building_config.new_building_space(building_type));
+    board.record_buildings_when_built(idx);
+
+    Ok(())
+}
+
+// =============================================================================
+impl Game {
+    /// Queue the effects of a building a player just constructed, both on the
+    /// player and on anyone watching for that building.
+    pub(crate) fn trigger_effects(
+        &mut self,
+        player: usize,
+        building_type: BuildingType,
+    ) {
+        if building_type == BuildingType::Magenta {
+            let effects = on_build_effects(self.players[player].monument());
+            self.players[player].pending_effects.extend(effects);
+        }
+
+        for (other, player_state) in self.players.iter_mut().enumerate() {
+            if other == player || player_state.is_finished() {
+                continue;
+            }
+            if let Some(position) = player_state
+                .watched_buildings
+                .iter()
+                .position(|watched| *watched == building_type)
+            {
+                player_state.watched_buildings.remove(position);
+                player_state
+                    .pending_effects
+                    .push(PendingEffect::PlaceWatchedBuilding(building_type));
+            }
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    /// Resolve the oldest pending effect of a player.
+    pub fn resolve_effect(
+        &mut self,
+        player: usize,
+        resolution: Resolution,
+    ) -> Result<(), GameError> {
+        let player_state = self
+            .players
+            .get_mut(player)
+            .ok_or(GameError::NoSuchPlayer(player))?;
+        let building_config = &player_state.building_config;
+        let effect = *player_state
+            .pending_effects
+            .first()
+            .ok_or(GameError::NoPendingEffect(player))?;
+        let board = &mut player_state.board;
+
+        let remaining_effect = match (effect, resolution) {
+            (_, Resolution::Skip) => {
+                if !effect.is_optional()
+                    && board.spaces().contains(&Space::Empty)
+                {
+                    return Err(GameError::WrongResolution(effect));
+                }
+                None
+            }
+            (
+                PendingEffect::ReplaceBuildings { remaining },
+                Resolution::Replace { idx, building_type },
+            ) => {
+                replace_building(board, building_config, idx, building_type)?;
+                if remaining > 1 {
+                    Some(PendingEffect::ReplaceBuildings {
+                        remaining: remaining - 1,
+                    })
+                } else {
+                    None
+                }
+            }
+            (
+                PendingEffect::PlaceBuilding,
+                Resolution::Place { idx, building_type },
+            ) => {
+                place_building(board, building_config, idx, building_type)?;
+                None
+            }
+            (
+                PendingEffect::PlaceWatchedBuilding(watched),
+                Resolution::Place { idx, building_type },
+            ) => {
+                if building_type != watched {
+                    return Err(GameError::WrongResolution(effect));
+                }
+                place_building(board, building_config, idx, building_type)?;
+                None
+            }
+            (PendingEffect::WatchBuildings, Resolution::Watch(buildings)) => {
+                let mut unique = buildings.clone();
+                unique.sort_by_key(|building_type| *building_type as usize);
+                unique.dedup();
+                if buildings.len() != OPALEYES_WATCH_BUILDINGS
+                    || unique.len() != buildings.len()
+                {
+                    return Err(GameError::WrongResolution(effect));
+                }
+                if buildings.contains(&BuildingType::Magenta) {
+                    return Err(GameError::MonumentNotAllowed);
+                }
+                player_state.watched_buildings = buildings;
+                None
+            }
+            _ => return Err(GameError::WrongResolution(effect)),
+        };
+
+        match remaining_effect {
+            Some(remaining_effect) => {
+                player_state.pending_effects[0] = remaining_effect;
+            }
+            None => {
+                player_state.pending_effects.remove(0);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::construct::ConstructError;
+    use crate::board::pattern::PatternMatch;
+    use crate::board::space::Resource;
+    use crate::building_config::{
+        BlackBuilding, BlueBuilding, GrayBuilding, GreenBuilding,
+        OrangeBuilding, RedBuilding, YellowBuilding,
+    };
+
+    // -------------------------------------------------------------------------
+    fn new_game(magenta: MagentaBuilding) -> Game {
+        let building_config = BuildingConfig::new(
+            BlackBuilding::Warehouse,
+            BlueBuilding::Cottage,
+            GrayBuilding::Well,
+            GreenBuilding::Tavern,
+            magenta,
+            OrangeBuilding::Chapel,
+            RedBuilding::Farm,
+            YellowBuilding::Tailor,
+        );
+
+        Game::new(building_config, 2, 4, 4)
+    }
+
+    // -------------------------------------------------------------------------
+    /// Give player #0 a monument by hand and trigger its effects.
+    fn build_monument(game: &mut Game, idx: usize) {
+        game.players[0].board.place(idx, BuildingType::Magenta);
+        game.trigger_effects(0, BuildingType::Magenta);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_architects_guild() {
+        let mut game = new_game(MagentaBuilding::ArchitectsGuild);
+        game.players[0].board.place(1, BuildingType::Blue);
+        game.players[0].board.place(2, BuildingType::Gray);
+        game.players[0].board.place(3, BuildingType::Red);
+        build_monument(&mut game, 0);
+        assert_eq!(
+            game.players()[0].pending_effects(),
+            &vec![PendingEffect::ReplaceBuildings { remaining: 2 }]
+        );
+
+        let replace =
+            |idx, building_type| Resolution::Replace { idx, building_type };
+        assert_eq!(
+            game.resolve_effect(0, replace(0, BuildingType::Blue)),
+            Err(GameError::MonumentNotAllowed)
+        );
+        assert_eq!(
+            game.resolve_effect(0, replace(1, BuildingType::Magenta)),
+            Err(GameError::MonumentNotAllowed)
+        );
+        assert_eq!(
+            game.resolve_effect(0, replace(1, BuildingType::Blue)),
+            Err(GameError::SameBuildingType(BuildingType::Blue))
+        );
+        assert_eq!(
+            game.resolve_effect(0, replace(4, BuildingType::Blue)),
+            Err(GameError::NotABuilding(4))
+        );
+
+        game.resolve_effect(0, replace(1, BuildingType::Green))
+            .unwrap();
+        game.resolve_effect(0, replace(2, BuildingType::Yellow))
+            .unwrap();
+        let board = game.board(0).unwrap();
+        assert!(board.spaces()[1].building_type_eq(BuildingType::Green));
+        assert!(board.spaces()[2].building_type_eq(BuildingType::Yellow));
+        assert_eq!(
+            game.resolve_effect(0, replace(3, BuildingType::Blue)),
+            Err(GameError::NoPendingEffect(0))
+        );
+
+        // Replacements are optional.
+        build_monument(&mut game, 4);
+        game.resolve_effect(0, Resolution::Skip).unwrap();
+        assert!(game.players()[0].pending_effects().is_empty());
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_grove_university() {
+        let mut game = new_game(MagentaBuilding::GroveUniversity);
+        build_monument(&mut game, 0);
+        assert_eq!(
+            game.resolve_effect(0, Resolution::Skip),
+            Err(GameError::WrongResolution(PendingEffect::PlaceBuilding))
+        );
+        assert_eq!(
+            game.resolve_effect(
+                0,
+                Resolution::Place {
+                    idx: 0,
+                    building_type: BuildingType::Blue
+                }
+            ),
+            Err(GameError::SpaceNotEmpty(0))
+        );
+        game.resolve_effect(
+            0,
+            Resolution::Place {
+                idx: 1,
+                building_type: BuildingType::Black,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            game.board(0).unwrap().spaces()[1],
+            Space::BuildingWithResources(BuildingType::Black, Vec::new())
+        );
+
+        // The round cannot end with an unresolved effect.
+        build_monument(&mut game, 2);
+        game.name_resource(Resource::Wood).unwrap();
+        game.place_resource(0, 3).unwrap();
+        game.place_resource(1, 3).unwrap();
+        assert_eq!(game.end_round(), Err(GameError::RoundNotFinished));
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_obelisk_of_the_crescent() {
+        let mut game = new_game(MagentaBuilding::ObeliskOfTheCrescent);
+        let well = PatternMatch::new(BuildingType::Gray, [0, 1].into());
+        for player in 0..2 {
+            game.players[player].board.place(0, Resource::Wood);
+            game.players[player].board.place(1, Resource::Stone);
+        }
+        assert_eq!(
+            game.construct(1, &well, 15, BuildingType::Gray),
+            Err(GameError::Construct(ConstructError::IdxNotInPattern(15)))
+        );
+
+        game.players[0].board.place(5, BuildingType::Magenta);
+        game.construct(0, &well, 15, BuildingType::Gray).unwrap();
+        let board = game.board(0).unwrap();
+        assert_eq!(board.spaces()[0], Space::Empty);
+        assert_eq!(board.spaces()[1], Space::Empty);
+        assert!(board.spaces()[15].building_type_eq(BuildingType::Gray));
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_opaleyes_watch() {
+        let mut game = new_game(MagentaBuilding::OpaleyesWatch);
+        build_monument(&mut game, 0);
+        assert_eq!(
+            game.resolve_effect(
+                0,
+                Resolution::Watch(vec![BuildingType::Blue, BuildingType::Blue])
+            ),
+            Err(GameError::WrongResolution(PendingEffect::WatchBuildings))
+        );
+        game.resolve_effect(
+            0,
+            Resolution::Watch(vec![
+                BuildingType::Blue,
+                BuildingType::Gray,
+                BuildingType::Red,
+            ]),
+        )
+        .unwrap();
+        assert_eq!(game.players()[0].watched_buildings().len(), 3);
+
+        // Player #1 builds a well.
+        game.players[1].board.place(0, Resource::Wood);
+        game.players[1].board.place(1, Resource::Stone);
+        let well = PatternMatch::new(BuildingType::Gray, [0, 1].into());
+        game.construct(1, &well, 0, BuildingType::Gray).unwrap();
+        assert_eq!(
+            game.players()[0].pending_effects(),
+            &vec![PendingEffect::PlaceWatchedBuilding(BuildingType::Gray)]
+        );
+        assert_eq!(
+            game.resolve_effect(
+                0,
+                Resolution::Place {
+                    idx: 1,
+                    building_type: BuildingType::Red
+                }
+            ),
+            Err(GameError::WrongResolution(
+                PendingEffect::PlaceWatchedBuilding(BuildingType::Gray)
+            ))
+        );
+        game.resolve_effect(
+            0,
+            Resolution::Place {
+                idx: 1,
+                building_type: BuildingType::Gray,
+            },
+        )
+        .unwrap();
+        assert!(game.board(0).unwrap().spaces()[1]
+            .building_type_eq(BuildingType::Gray));
+        assert_eq!(
+            game.players()[0].watched_buildings(),
+            &vec![BuildingType::Blue, BuildingType::Red]
+        );
+    }
+}
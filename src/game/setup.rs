@@ -0,0 +1,162 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use strum::IntoEnumIterator;
+
+use crate::building_config::MagentaBuilding;
+use crate::game::agent::Agent;
+use crate::game::{Game, GameError};
+
+/// The number of monuments dealt to each player, who keeps one of them.
+pub const MONUMENTS_PER_HAND: usize = 2;
+
+// -----------------------------------------------------------------------------
+/// Deal `MONUMENTS_PER_HAND` different monuments to each of `num_players`
+/// players from a shuffled deck of every monument.
+pub fn deal_monuments<R: Rng>(
+    num_players: usize,
+    rng: &mut R,
+) -> Result<Vec<Vec<MagentaBuilding>>, GameError> {
+    let mut deck: Vec<MagentaBuilding> = MagentaBuilding::iter().collect();
+    if num_players * MONUMENTS_PER_HAND > deck.len() {
+        return Err(GameError::TooManyPlayers(num_players));
+    }
+    deck.shuffle(rng);
+
+    let hands = deck
+        .chunks(MONUMENTS_PER_HAND)
+        .take(num_players)
+        .map(|hand| hand.to_vec())
+        .collect();
+
+    Ok(hands)
+}
+
+// =============================================================================
+impl Game {
+    /// Deal each player a hand of monuments to choose their own from. The game
+    /// cannot start until every player has kept one.
+    pub fn deal_monuments<R: Rng>(
+        &mut self,
+        rng: &mut R,
+    ) -> Result<(), GameError> {
+        let hands = deal_monuments(self.players.len(), rng)?;
+        for (player, hand) in self.players.iter_mut().zip(hands) {
+            player.monument_hand = hand;
+        }
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Keep `monument` from a player's hand as their monument, and discard the
+    /// rest of the hand.
+    pub fn keep_monument(
+        &mut self,
+        player: usize,
+        monument: MagentaBuilding,
+    ) -> Result<(), GameError> {
+        let player_state = self
+            .players
+            .get(player)
+            .ok_or(GameError::NoSuchPlayer(player))?;
+        if !player_state.monument_hand.contains(&monument) {
+            return Err(GameError::MonumentNotInHand(monument));
+        }
+        self.set_monument(player, monument)?;
+        self.players[player].monument_hand.clear();
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    /// Deal the monuments and ask each player's agent which one to keep.
+    pub fn choose_monuments<R: Rng>(
+        &mut self,
+        agents: &mut [Box<dyn Agent>],
+        rng: &mut R,
+    ) -> Result<(), GameError> {
+        self.check_agents(agents)?;
+        self.deal_monuments(rng)?;
+        for (player, agent) in agents.iter_mut().enumerate() {
+            let hand = self.players[player].monument_hand.clone();
+            let monument = agent.keep_monument(self, player, &hand);
+            self.keep_monument(player, monument)?;
+        }
+
+        Ok(())
+    }
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::board::space::Resource;
+    use crate::building_config::BuildingConfig;
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_deal_monuments() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let hands = deal_monuments(6, &mut rng).unwrap();
+        assert_eq!(hands.len(), 6);
+        assert!(hands.iter().all(|hand| hand.len() == MONUMENTS_PER_HAND));
+
+        // No monument is dealt twice.
+        let dealt = hands.concat();
+        let unique: HashSet<usize> =
+            dealt.iter().map(|monument| *monument as usize).collect();
+        assert_eq!(unique.len(), dealt.len());
+
+        // The same seed deals the same hands.
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(deal_monuments(6, &mut rng), Ok(hands));
+
+        // There are not enough monuments for everyone in a larger game.
+        assert_eq!(
+            deal_monuments(8, &mut rng),
+            Err(GameError::TooManyPlayers(8))
+        );
+        let mut game = Game::new(BuildingConfig::for_test(), 8, 4, 4);
+        assert_eq!(
+            game.deal_monuments(&mut rng),
+            Err(GameError::TooManyPlayers(8))
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_keep_monument() {
+        let mut game = Game::new(BuildingConfig::for_test(), 2, 4, 4);
+        let mut rng = StdRng::seed_from_u64(1);
+        game.deal_monuments(&mut rng).unwrap();
+        let hand = game.players()[0].monument_hand().clone();
+        assert_eq!(hand.len(), MONUMENTS_PER_HAND);
+
+        // The game cannot start until every player has chosen.
+        assert_eq!(
+            game.name_resource(Resource::Wood),
+            Err(GameError::MonumentNotChosen(0))
+        );
+
+        let not_dealt = MagentaBuilding::iter()
+            .find(|monument| !hand.contains(monument))
+            .unwrap();
+        assert_eq!(
+            game.keep_monument(0, not_dealt),
+            Err(GameError::MonumentNotInHand(not_dealt))
+        );
+        game.keep_monument(0, hand[1]).unwrap();
+        assert_eq!(game.players()[0].monument(), hand[1]);
+        assert!(game.players()[0].monument_hand().is_empty());
+
+        let other_hand = game.players()[1].monument_hand().clone();
+        game.keep_monument(1, other_hand[0]).unwrap();
+        game.name_resource(Resource::Wood).unwrap();
+    }
+}
@@ -0,0 +1,460 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use strum::IntoEnumIterator;
+
+use crate::board::space::Resource;
+use crate::building_config::BuildingConfig;
+use crate::game::agent::Agent;
+use crate::game::{Game, GameError};
+use crate::score::{ScoreCard, ScoringContext};
+
+/// The number of cards of each resource in the resource deck.
+pub const CARDS_PER_RESOURCE: usize = 3;
+
+/// The number of cards drawn before the deck is refreshed.
+pub const CARDS_PER_CYCLE: usize = 3;
+
+// =============================================================================
+/// The solo resource deck. Each round the top card is drawn and its resource
+/// must be placed. After every third card, the first two cards of the cycle
+/// are discarded and the third is put back on the bottom of the deck. Once the
+/// deck runs out, the discards are shuffled to form a new deck.
+pub struct ResourceDeck {
+    deck: Vec<Resource>,
+    cycle: Vec<Resource>,
+    discards: Vec<Resource>,
+    // The number of cards at the bottom of the deck that were put back there
+    // and so are known to the player.
+    returned: usize,
+    rng: ChaCha8Rng,
+}
+
+impl ResourceDeck {
+    pub fn new(seed: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut deck: Vec<Resource> = Resource::iter()
+            .flat_map(|resource| [resource; CARDS_PER_RESOURCE])
+            .collect();
+        deck.shuffle(&mut rng);
+
+        Self {
+            deck,
+            cycle: Vec::new(),
+            discards: Vec::new(),
+            returned: 0,
+            rng,
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the cards left to draw, from the top of the deck down.
+    pub fn deck(&self) -> &Vec<Resource> {
+        &self.deck
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the cards drawn so far in the current cycle.
+    pub fn cycle(&self) -> &Vec<Resource> {
+        &self.cycle
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn discards(&self) -> &Vec<Resource> {
+        &self.discards
+    }
+
+    // -------------------------------------------------------------------------
+    /// Draw the top card of the deck.
+    pub fn draw(&mut self) -> Resource {
+        if self.deck.is_empty() {
+            self.deck.append(&mut self.discards);
+            self.deck.shuffle(&mut self.rng);
+        }
+        if self.deck.len() == self.returned {
+            self.returned -= 1;
+        }
+        let resource = self.deck.remove(0);
+        self.cycle.push(resource);
+
+        if self.cycle.len() == CARDS_PER_CYCLE {
+            let kept = self.cycle.pop().unwrap();
+            self.discards.append(&mut self.cycle);
+            self.deck.push(kept);
+            self.returned += 1;
+        }
+
+        resource
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the number of cards of each resource left in the deck.
+    pub fn remaining(&self) -> HashMap<Resource, usize> {
+        let remaining = count_resources(&self.deck);
+
+        remaining
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the chance of drawing each resource next, as far as the player
+    /// can tell. Cards put back on the bottom of the deck are known, so they
+    /// are only drawn once the shuffled cards above them run out.
+    pub fn draw_probabilities(&self) -> HashMap<Resource, f64> {
+        let unseen = &self.deck[..self.deck.len() - self.returned];
+        let candidates = if !unseen.is_empty() {
+            unseen
+        } else if !self.deck.is_empty() {
+            &self.deck[..1]
+        } else {
+            &self.discards[..]
+        };
+
+        let counts = count_resources(candidates);
+        let probabilities = Resource::iter()
+            .map(|resource| {
+                let probability =
+                    counts[&resource] as f64 / candidates.len() as f64;
+                (resource, probability)
+            })
+            .collect();
+
+        probabilities
+    }
+}
+
+// -----------------------------------------------------------------------------
+/// Count the cards of each resource in `cards`, including those with none.
+fn count_resources(cards: &[Resource]) -> HashMap<Resource, usize> {
+    let counts = Resource::iter()
+        .map(|resource| {
+            let count = cards.iter().filter(|card| **card == resource).count();
+            (resource, count)
+        })
+        .collect();
+
+    counts
+}
+
+// =============================================================================
+/// The titles awarded for a solo game, from best to worst.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SoloRank {
+    MasterArchitect,
+    ExpertPlanner,
+    TalentedBuilder,
+    AdeptDesigner,
+    AspiringApprentice,
+    UnskilledLaborer,
+}
+
+/// The lowest score needed for each title, from best to worst.
+const MIN_SCORES_BY_SOLO_RANK: [(i32, SoloRank); 5] = [
+    (38, SoloRank::MasterArchitect),
+    (32, SoloRank::ExpertPlanner),
+    (25, SoloRank::TalentedBuilder),
+    (18, SoloRank::AdeptDesigner),
+    (10, SoloRank::AspiringApprentice),
+];
+
+impl SoloRank {
+    /// Return the title earned by a final score of `score`.
+    pub fn from_score(score: i32) -> Self {
+        let rank = MIN_SCORES_BY_SOLO_RANK
+            .iter()
+            .find(|(min_score, _)| score >= *min_score)
+            .map(|(_, rank)| *rank)
+            .unwrap_or(SoloRank::UnskilledLaborer);
+
+        rank
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn title(&self) -> &str {
+        match self {
+            SoloRank::MasterArchitect => "Master Architect",
+            SoloRank::ExpertPlanner => "Expert Planner",
+            SoloRank::TalentedBuilder => "Talented Builder",
+            SoloRank::AdeptDesigner => "Adept Designer",
+            SoloRank::AspiringApprentice => "Aspiring Apprentice",
+            SoloRank::UnskilledLaborer => "Unskilled Laborer",
+        }
+    }
+}
+
+impl fmt::Display for SoloRank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.title())
+    }
+}
+
+// =============================================================================
+/// A single-player game. Resources are drawn from the resource deck instead of
+/// being named, and the game ends once the town is complete.
+pub struct SoloGame {
+    game: Game,
+    resource_deck: ResourceDeck,
+}
+
+impl SoloGame {
+    pub fn new(
+        building_config: BuildingConfig,
+        rows: usize,
+        cols: usize,
+        seed: u64,
+    ) -> Self {
+        Self {
+            game: Game::new(building_config, 1, rows, cols),
+            resource_deck: ResourceDeck::new(seed),
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn resource_deck(&self) -> &ResourceDeck {
+        &self.resource_deck
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn is_over(&self) -> bool {
+        self.game.is_over()
+    }
+
+    // -------------------------------------------------------------------------
+    /// Draw a resource card and play the round with it.
+    pub fn play_round(
+        &mut self,
+        agent: &mut Box<dyn Agent>,
+    ) -> Result<(), GameError> {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        let resource = self.resource_deck.draw();
+        self.game
+            .play_round_with(std::slice::from_mut(agent), resource)
+    }
+
+    // -------------------------------------------------------------------------
+    /// Play rounds until the town is complete.
+    pub fn play(
+        &mut self,
+        agent: &mut Box<dyn Agent>,
+    ) -> Result<(), GameError> {
+        while !self.is_over() {
+            self.play_round(agent)?;
+        }
+
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn score(&self, scoring_context: &ScoringContext) -> ScoreCard {
+        let score_card = self.game.score(scoring_context).remove(0);
+
+        score_card
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the title earned by the town.
+    pub fn rank(&self, scoring_context: &ScoringContext) -> SoloRank {
+        SoloRank::from_score(self.score(scoring_context).score_all())
+    }
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::pattern::PatternMatch;
+    use crate::board::space::Space;
+    use crate::building_config::generate::Card;
+    use crate::building_config::{BlackBuilding, MagentaBuilding};
+    use crate::game::black::Placement;
+    use crate::game::monument::{PendingEffect, Resolution};
+
+    // -------------------------------------------------------------------------
+    /// Places resources in reading order and constructs the first building it
+    /// can.
+    struct GreedyAgent;
+
+    impl Agent for GreedyAgent {
+        fn name_resource(&mut self, _game: &Game, _player: usize) -> Resource {
+            unreachable!("resources are drawn in a solo game")
+        }
+
+        fn place_resource(
+            &mut self,
+            game: &Game,
+            player: usize,
+            _resource: Resource,
+        ) -> Placement {
+            let board = game.board(player).unwrap();
+            let idx = (0..board.elems())
+                .find(|idx| board.spaces()[*idx] == Space::Empty)
+                .unwrap();
+            Placement::Space(idx)
+        }
+
+        fn construct(
+            &mut self,
+            game: &Game,
+            player: usize,
+        ) -> Option<(PatternMatch, usize)> {
+            let board = game.board(player).unwrap();
+            let pattern_match = board
+                .matching_patterns(game.players()[player].building_config())
+                .into_iter()
+                .next()?;
+            let idx = *pattern_match.idxs().iter().min().unwrap();
+            Some((pattern_match, idx))
+        }
+
+        fn assign_resource(
+            &mut self,
+            _game: &Game,
+            _player: usize,
+            _idx: usize,
+        ) -> Resource {
+            Resource::Wood
+        }
+
+        fn resolve_effect(
+            &mut self,
+            _game: &Game,
+            _player: usize,
+            _effect: PendingEffect,
+        ) -> Resolution {
+            Resolution::Skip
+        }
+
+        fn keep_monument(
+            &mut self,
+            _game: &Game,
+            _player: usize,
+            hand: &[MagentaBuilding],
+        ) -> MagentaBuilding {
+            hand[0]
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_resource_deck() {
+        let mut resource_deck = ResourceDeck::new(3);
+        assert_eq!(resource_deck.deck().len(), 15);
+        for resource in Resource::iter() {
+            let count = resource_deck
+                .deck()
+                .iter()
+                .filter(|card| **card == resource)
+                .count();
+            assert_eq!(count, CARDS_PER_RESOURCE);
+        }
+
+        // The third card of a cycle goes back on the bottom of the deck.
+        let first = resource_deck.draw();
+        let second = resource_deck.draw();
+        assert_eq!(resource_deck.cycle(), &vec![first, second]);
+        let third = resource_deck.draw();
+        assert!(resource_deck.cycle().is_empty());
+        assert_eq!(resource_deck.discards(), &vec![first, second]);
+        assert_eq!(resource_deck.deck().len(), 13);
+        assert_eq!(resource_deck.deck().last(), Some(&third));
+
+        // The discards are shuffled back in once the deck runs out.
+        for _ in 0..100 {
+            resource_deck.draw();
+            let num_cards = resource_deck.deck().len()
+                + resource_deck.cycle().len()
+                + resource_deck.discards().len();
+            assert_eq!(num_cards, 15);
+        }
+
+        // The same seed draws the same cards.
+        let mut resource_deck = ResourceDeck::new(3);
+        let mut other = ResourceDeck::new(3);
+        assert!((0..50).all(|_| resource_deck.draw() == other.draw()));
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_draw_probabilities() {
+        let mut resource_deck = ResourceDeck::new(5);
+        let remaining = resource_deck.remaining();
+        assert!(remaining.values().all(|count| *count == CARDS_PER_RESOURCE));
+        let probabilities = resource_deck.draw_probabilities();
+        assert!(probabilities
+            .values()
+            .all(|probability| (probability - 0.2).abs() < 1e-9));
+
+        let first = resource_deck.draw();
+        assert_eq!(resource_deck.remaining()[&first], CARDS_PER_RESOURCE - 1);
+        let probability = resource_deck.draw_probabilities()[&first];
+        assert!((probability - 2.0 / 14.0).abs() < 1e-9);
+
+        // Once only the cards put back remain, the next draw is known.
+        for _ in 0..14 {
+            resource_deck.draw();
+        }
+        assert_eq!(resource_deck.deck().len(), 5);
+        let next = resource_deck.deck()[0];
+        assert_eq!(resource_deck.draw_probabilities()[&next], 1.0);
+        assert_eq!(resource_deck.draw(), next);
+
+        // With the deck empty, the next draw comes from the discards.
+        while !resource_deck.deck().is_empty() {
+            resource_deck.draw();
+        }
+        let discards = resource_deck.discards().clone();
+        let probabilities = resource_deck.draw_probabilities();
+        for resource in Resource::iter() {
+            let count =
+                discards.iter().filter(|card| **card == resource).count();
+            let expected = count as f64 / discards.len() as f64;
+            assert!((probabilities[&resource] - expected).abs() < 1e-9);
+        }
+        let total: f64 = probabilities.values().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_solo_rank() {
+        assert_eq!(SoloRank::from_score(40), SoloRank::MasterArchitect);
+        assert_eq!(SoloRank::from_score(38), SoloRank::MasterArchitect);
+        assert_eq!(SoloRank::from_score(37), SoloRank::ExpertPlanner);
+        assert_eq!(SoloRank::from_score(25), SoloRank::TalentedBuilder);
+        assert_eq!(SoloRank::from_score(24), SoloRank::AdeptDesigner);
+        assert_eq!(SoloRank::from_score(10), SoloRank::AspiringApprentice);
+        assert_eq!(SoloRank::from_score(9), SoloRank::UnskilledLaborer);
+        assert_eq!(SoloRank::from_score(-5), SoloRank::UnskilledLaborer);
+        assert_eq!(SoloRank::ExpertPlanner.to_string(), "Expert Planner");
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_play() {
+        let building_config = BuildingConfig::for_test()
+            .with_card(Card::Black(BlackBuilding::Bank));
+        let mut solo_game = SoloGame::new(building_config, 4, 4, 11);
+        let mut agent: Box<dyn Agent> = Box::new(GreedyAgent);
+        solo_game.play(&mut agent).unwrap();
+        assert!(solo_game.is_over());
+        assert_eq!(solo_game.play_round(&mut agent), Err(GameError::GameOver));
+
+        let board = solo_game.game().board(0).unwrap();
+        assert!(!board.spaces().contains(&Space::Empty));
+        let scoring_context = ScoringContext::default();
+        let score = solo_game.score(&scoring_context).score_all();
+        assert_eq!(
+            solo_game.rank(&scoring_context),
+            SoloRank::from_score(score)
+        );
+    }
+}
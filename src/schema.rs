@@ -0,0 +1,170 @@
+use std::error;
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// The version of the JSON schema written by `to_json`. Bump it whenever the
+/// serialized form of a `Board`, `BuildingConfig`, `ScoringContext` or
+/// `ScoreCard` changes in a way older readers cannot handle.
+pub const SCHEMA_VERSION: u32 = 1;
+
+// =============================================================================
+/// Reasons a value cannot be written to or read from JSON.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SchemaError {
+    /// The JSON is malformed or does not describe the expected type.
+    Json(String),
+    /// The JSON was written with a schema this version cannot read.
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Json(msg) => write!(f, "invalid JSON: {msg}"),
+            SchemaError::UnsupportedVersion(version) => write!(
+                f,
+                "schema version {version} is not supported, expected \
+                 {SCHEMA_VERSION}"
+            ),
+        }
+    }
+}
+
+impl error::Error for SchemaError {}
+
+impl From<serde_json::Error> for SchemaError {
+    fn from(err: serde_json::Error) -> Self {
+        SchemaError::Json(err.to_string())
+    }
+}
+
+impl From<SchemaError> for Error {
+    fn from(err: SchemaError) -> Self {
+        Error::Schema(err)
+    }
+}
+
+// =============================================================================
+/// A value together with the schema version it was written with.
+#[derive(Deserialize, Serialize)]
+struct Versioned<T> {
+    schema_version: u32,
+    data: T,
+}
+
+/// Used to read the version before the data, so that data written with another
+/// schema is reported as such rather than as malformed.
+#[derive(Deserialize)]
+struct Version {
+    schema_version: u32,
+}
+
+// -----------------------------------------------------------------------------
+/// Write `value` as JSON of the form
+/// `{"schema_version": SCHEMA_VERSION, "data": ...}`.
+pub fn to_json<T: Serialize>(value: &T) -> Result<String, SchemaError> {
+    let versioned = Versioned {
+        schema_version: SCHEMA_VERSION,
+        data: value,
+    };
+    let json = serde_json::to_string(&versioned)?;
+
+    Ok(json)
+}
+
+// -----------------------------------------------------------------------------
+/// Read a value written by `to_json`.
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, SchemaError> {
+    let version: Version = serde_json::from_str(json)?;
+    if version.schema_version != SCHEMA_VERSION {
+        return Err(SchemaError::UnsupportedVersion(version.schema_version));
+    }
+    let versioned: Versioned<T> = serde_json::from_str(json)?;
+
+    Ok(versioned.data)
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::space::{BuildingType, Resource};
+    use crate::board::Board;
+    use crate::building_config::generate::Card;
+    use crate::building_config::{
+        BuildingConfig, GreenBuilding, OrangeBuilding, RedBuilding,
+    };
+    use crate::score::{score, ScoreCard, ScoringContext};
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_round_trip() {
+        let building_config = BuildingConfig::for_test()
+            .with_card(Card::Green(GreenBuilding::Almshouse))
+            .with_card(Card::Orange(OrangeBuilding::Cloister))
+            .with_card(Card::Red(RedBuilding::Granary));
+        let json = to_json(&building_config).unwrap();
+        let parsed: BuildingConfig = from_json(&json).unwrap();
+        assert_eq!(parsed, building_config);
+
+        let mut board = Board::new(4, 4);
+        board.place(0, BuildingType::Orange);
+        board.place(5, Resource::Wood);
+        board.place(13, (BuildingType::Black, vec![Resource::Glass], 3));
+        board.place(14, (BuildingType::Black, None));
+        board.record_buildings_when_built(0);
+        board.set_finish_position(Some(2));
+        let json = to_json(&board).unwrap();
+        let parsed: Board = from_json(&json).unwrap();
+        assert_eq!(parsed.rows(), 4);
+        assert_eq!(parsed.elems(), 16);
+        assert_eq!(parsed.spaces(), board.spaces());
+        assert_eq!(parsed.buildings_when_built(0), Some(3));
+        assert_eq!(parsed.finish_position(), Some(2));
+
+        let scoring_context = ScoringContext::default();
+        let json = to_json(&scoring_context).unwrap();
+        let parsed: ScoringContext = from_json(&json).unwrap();
+        assert_eq!(parsed, scoring_context);
+
+        let score_card =
+            score(&board, &building_config, &scoring_context, None);
+        let json = to_json(&score_card).unwrap();
+        let parsed: ScoreCard = from_json(&json).unwrap();
+        assert_eq!(parsed.flatten(), score_card.flatten());
+        assert_eq!(parsed.score_all(), score_card.score_all());
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_from_json_errors() {
+        let json = r#"{"schema_version":99,"data":"Wood"}"#;
+        assert_eq!(
+            from_json::<Resource>(json),
+            Err(SchemaError::UnsupportedVersion(99))
+        );
+        let json = r#"{"schema_version":1,"data":"Wood"}"#;
+        assert_eq!(from_json::<Resource>(json), Ok(Resource::Wood));
+        assert!(matches!(
+            from_json::<Resource>(r#"{"data":"Wood"}"#),
+            Err(SchemaError::Json(_))
+        ));
+
+        // A board must have exactly as many spaces as its size implies.
+        let json = r#"{"schema_version":1,"data":{
+            "rows":3,"cols":3,"spaces":["Empty","Empty"]
+        }}"#;
+        assert!(matches!(
+            from_json::<Board>(json),
+            Err(SchemaError::Json(msg)) if msg.contains("expected 9 spaces")
+        ));
+        let json = r#"{"schema_version":1,"data":{
+            "rows":2,"cols":3,"spaces":[]
+        }}"#;
+        assert!(from_json::<Board>(json).is_err());
+    }
+}
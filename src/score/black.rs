@@ -3,26 +3,24 @@ use std::collections::HashMap;
 use crate::board::space::BuildingType;
 use crate::board::Board;
 use crate::building_config::{BlackBuilding, BuildingConfig};
-use crate::score::{score_per_each, ScoringContext};
+use crate::score::explain::Reason;
+use crate::score::{explain_per_each, score_per_each, ScoringContext};
 
 // -------------------------------------------------------------------------
 fn score_warehouses(
     board: &Board,
     scoring_context: &ScoringContext,
 ) -> HashMap<usize, i32> {
-    let scores = board.spaces().iter().enumerate().fold(
-        HashMap::new(),
-        |mut scores, (idx, space)| {
-            if let Some(resources) = space.resources() {
-                scores.insert(
-                    idx,
-                    resources.len() as i32
-                        * scoring_context.points_per_resource_on_warehouse,
-                );
-            }
-            scores
-        },
-    );
+    let scores = board
+        .building_mask(BuildingType::Black)
+        .iter()
+        .filter_map(|idx| {
+            let resources = board.spaces()[idx].resources()?;
+            let points = resources.len() as i32
+                * scoring_context.points_per_resource_on_warehouse;
+            Some((idx, points))
+        })
+        .collect();
 
     scores
 }
@@ -55,6 +53,30 @@ pub fn score(
     scores
 }
 
+// -----------------------------------------------------------------------------
+pub fn explain(
+    board: &Board,
+    building_config: &BuildingConfig,
+) -> HashMap<usize, Reason> {
+    let reasons = match building_config.black() {
+        BlackBuilding::Bank
+        | BlackBuilding::Factory
+        | BlackBuilding::TradingPost => {
+            explain_per_each(board, BuildingType::Black, |_| Reason::Flat)
+        }
+        BlackBuilding::Warehouse => board
+            .building_mask(BuildingType::Black)
+            .iter()
+            .filter_map(|idx| {
+                let resources = board.spaces()[idx].resources()?;
+                Some((idx, Reason::ResourcesStored(resources.len())))
+            })
+            .collect(),
+    };
+
+    reasons
+}
+
 // =============================================================================
 #[cfg(test)]
 mod test {
@@ -1,21 +1,36 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
+use crate::board::bitboard::Bitmask;
 use crate::board::space::BuildingType;
 use crate::board::Board;
 use crate::building_config::{BlueBuilding, BuildingConfig, MagentaBuilding};
-use crate::score::{score_if_in_idx_set, score_per_each, ScoringContext};
+use crate::score::explain::Reason;
+use crate::score::{
+    explain_per_each, score_if_in_mask, score_per_each, ScoringContext,
+};
+
+// -----------------------------------------------------------------------------
+/// Return `true` if cottages score without being fed, as they do while the
+/// Grand Mausoleum of the Rodina is in town.
+fn is_fed_by_mausoleum(
+    board: &Board,
+    building_config: &BuildingConfig,
+) -> bool {
+    let is_fed_by_mausoleum = building_config.magenta()
+        == MagentaBuilding::GrandMausoleumOfTheRodina
+        && board.count_building_type(BuildingType::Magenta) > 0;
+
+    is_fed_by_mausoleum
+}
 
 // -----------------------------------------------------------------------------
 fn score_cottages(
     board: &Board,
     building_config: &BuildingConfig,
     scoring_context: &ScoringContext,
-    fed_idxs: &HashSet<usize>,
+    fed_idxs: &Bitmask,
 ) -> HashMap<usize, i32> {
-    let scores = if building_config.magenta()
-        == MagentaBuilding::GrandMausoleumOfTheRodina
-        && board.count_building_type(BuildingType::Magenta) > 0
-    {
+    let scores = if is_fed_by_mausoleum(board, building_config) {
         score_per_each(
             board,
             BuildingType::Blue,
@@ -23,7 +38,7 @@ fn score_cottages(
                 .points_per_cottage_with_grand_mausoleum_of_the_rodina,
         )
     } else {
-        score_if_in_idx_set(
+        score_if_in_mask(
             board,
             fed_idxs,
             BuildingType::Blue,
@@ -39,7 +54,7 @@ pub fn score(
     board: &Board,
     building_config: &BuildingConfig,
     scoring_context: &ScoringContext,
-    fed_idxs: &HashSet<usize>,
+    fed_idxs: &Bitmask,
 ) -> HashMap<usize, i32> {
     let score = match building_config.blue() {
         BlueBuilding::Cottage => {
@@ -50,6 +65,27 @@ pub fn score(
     score
 }
 
+// -----------------------------------------------------------------------------
+pub fn explain(
+    board: &Board,
+    building_config: &BuildingConfig,
+    fed_idxs: &Bitmask,
+) -> HashMap<usize, Reason> {
+    let reasons = match building_config.blue() {
+        BlueBuilding::Cottage => {
+            explain_per_each(board, BuildingType::Blue, |idx| {
+                if is_fed_by_mausoleum(board, building_config) {
+                    Reason::FedByMausoleum
+                } else {
+                    Reason::Fed(fed_idxs.contains(idx))
+                }
+            })
+        }
+    };
+
+    reasons
+}
+
 // =============================================================================
 #[cfg(test)]
 mod test {
@@ -85,7 +121,7 @@ mod test {
         board.place(19, BuildingType::Black);
         board.place(21, BuildingType::Red);
         board.place(24, BuildingType::Magenta);
-        let fed_idxs = HashSet::from([0, 1, 14]);
+        let fed_idxs = Bitmask::from_iter([0, 1, 14]);
         let expected = HashMap::from([(0, 3), (1, 3), (14, 3)]);
         assert_eq!(
             score_cottages(
@@ -111,7 +147,7 @@ mod test {
         );
 
         // Feed the added cottage.
-        let fed_idxs = HashSet::from([0, 1, 11, 14]);
+        let fed_idxs = Bitmask::from_iter([0, 1, 11, 14]);
         let expected = HashMap::from([(0, 3), (1, 3), (11, 3), (14, 3)]);
         assert_eq!(
             score_cottages(
@@ -135,7 +171,7 @@ mod test {
             YellowBuilding::Theater,
         );
         // Feed only one cottage.
-        let fed_idxs = HashSet::from([0]);
+        let fed_idxs = Bitmask::from_iter([0]);
         assert_eq!(
             score_cottages(
                 &board,
@@ -0,0 +1,210 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::board::space::BuildingType;
+
+// -----------------------------------------------------------------------------
+/// Return `building_types` as a comma-separated list, such as "red, yellow".
+fn list(building_types: &[BuildingType]) -> String {
+    let list = building_types
+        .iter()
+        .map(|building_type| format!("{building_type:?}").to_lowercase())
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    list
+}
+
+// =============================================================================
+/// Why a space scored what it did. Each variant records the facts about the
+/// town that its building's rule looked at.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Reason {
+    /// The building scores the same no matter where it is.
+    Flat,
+    /// The building feeds others and scores nothing itself.
+    Feeds,
+    /// The building scores only if it is fed.
+    Fed(bool),
+    /// Cottages score without being fed while the Grand Mausoleum of the
+    /// Rodina is in town.
+    FedByMausoleum,
+    /// A Warehouse loses points for each resource stored on it.
+    ResourcesStored(usize),
+    /// A Fountain scores if it is in a group of more than one.
+    ContiguousGroup(usize),
+    /// The building scores if it is adjacent to any of `wanted`; `found` are
+    /// the ones it is adjacent to.
+    AdjacentTo {
+        wanted: Vec<BuildingType>,
+        found: Vec<BuildingType>,
+    },
+    /// The building scores unless it is adjacent to any of `forbidden`;
+    /// `found` are the ones it is adjacent to.
+    NotAdjacentTo {
+        forbidden: Vec<BuildingType>,
+        found: Vec<BuildingType>,
+    },
+    /// A Well scores for each adjacent blue building.
+    AdjacentBlueBuildings(u32),
+    /// The first building of a type scored by count scores for all of them.
+    BuildingCount(u32),
+    /// The building was counted by the building at `idx`.
+    CountedAt(usize),
+    /// Feast Halls score more if the town has more than the neighbor's.
+    FeastHalls { count: u32, other_count: u32 },
+    /// An Inn scores if it is the only one in its row and column.
+    InnsInRowAndCol { row: u32, col: u32 },
+    /// A Chapel scores for each fed blue building in town.
+    FedBlueBuildings(i32),
+    /// Cloisters score for each Cloister in a corner.
+    CloistersInCorners(i32),
+    /// A Temple scores if it is adjacent to enough fed blue buildings.
+    AdjacentFedBlueBuildings { count: u32, needed: u32 },
+    /// A Market scores for each Market in its row or column, whichever has
+    /// more.
+    MarketsInRowOrCol(u32),
+    /// Tailors score more for each Tailor in the center.
+    TailorsInCenter(i32),
+    /// A Theater scores for each other building type in its row and column.
+    UniqueBuildingTypesInRowAndCol(usize),
+    /// The Archive of the Second Age scores for each other building type.
+    UniqueBuildingTypes(usize),
+    /// Mandras Palace scores for each adjacent building type.
+    UniqueAdjacentBuildingTypes(usize),
+    /// The Shrine of the Elder Tree scores by the number of buildings in town
+    /// when it was built, if known.
+    BuildingsWhenBuilt(Option<u32>),
+    /// Silva Forum scores for each building in the largest contiguous group
+    /// of one type.
+    LargestContiguousGroup(usize),
+    /// The Sky Baths score for each building type missing from the town.
+    MissingBuildingTypes(usize),
+    /// The Starloom scores by the position in which the town was finished,
+    /// if it was.
+    FinishPosition(Option<usize>),
+    /// The space has no building, whether or not a resource was left on it.
+    Unused,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reason::Flat => write!(f, "scores the same anywhere"),
+            Reason::Feeds => write!(f, "feeds buildings instead of scoring"),
+            Reason::Fed(true) => write!(f, "fed"),
+            Reason::Fed(false) => write!(f, "not fed"),
+            Reason::FedByMausoleum => {
+                write!(f, "scores unfed with the Grand Mausoleum in town")
+            }
+            Reason::ResourcesStored(num_resources) => {
+                write!(f, "{num_resources} resources stored")
+            }
+            Reason::ContiguousGroup(size) => {
+                write!(f, "in a contiguous group of {size}, needs 2")
+            }
+            Reason::AdjacentTo { wanted, found } if found.is_empty() => {
+                write!(f, "not adjacent to any of {}", list(wanted))
+            }
+            Reason::AdjacentTo { found, .. } => {
+                write!(f, "adjacent to {}", list(found))
+            }
+            Reason::NotAdjacentTo { forbidden, found } if found.is_empty() => {
+                write!(f, "not adjacent to any of {}", list(forbidden))
+            }
+            Reason::NotAdjacentTo { found, .. } => {
+                write!(f, "adjacent to {}", list(found))
+            }
+            Reason::AdjacentBlueBuildings(count) => {
+                write!(f, "adjacent to {count} blue buildings")
+            }
+            Reason::BuildingCount(count) => {
+                write!(f, "{count} in town, scored here")
+            }
+            Reason::CountedAt(_) => write!(f, "scored with the first one"),
+            Reason::FeastHalls { count, other_count } => write!(
+                f,
+                "{count} in town, {other_count} in the neighbor's town"
+            ),
+            Reason::InnsInRowAndCol { row, col } => {
+                write!(f, "{row} in its row and {col} in its column")
+            }
+            Reason::FedBlueBuildings(count) => {
+                write!(f, "{count} fed blue buildings in town")
+            }
+            Reason::CloistersInCorners(count) => {
+                write!(f, "{count} in corners")
+            }
+            Reason::AdjacentFedBlueBuildings { count, needed } => write!(
+                f,
+                "adjacent to {count} fed blue buildings, needs {needed}"
+            ),
+            Reason::MarketsInRowOrCol(count) => {
+                write!(f, "{count} in its row or column")
+            }
+            Reason::TailorsInCenter(count) => {
+                write!(f, "{count} in the center")
+            }
+            Reason::UniqueBuildingTypesInRowAndCol(count) => {
+                write!(f, "{count} other building types in its row and column")
+            }
+            Reason::UniqueBuildingTypes(count) => {
+                write!(f, "{count} other building types in town")
+            }
+            Reason::UniqueAdjacentBuildingTypes(count) => {
+                write!(f, "adjacent to {count} building types")
+            }
+            Reason::BuildingsWhenBuilt(Some(count)) => {
+                write!(f, "{count} buildings in town when built")
+            }
+            Reason::BuildingsWhenBuilt(None) => {
+                write!(f, "not known when it was built")
+            }
+            Reason::LargestContiguousGroup(size) => {
+                write!(f, "largest contiguous group = {size}")
+            }
+            Reason::MissingBuildingTypes(count) => {
+                write!(f, "{count} building types missing from town")
+            }
+            Reason::FinishPosition(Some(position)) => {
+                write!(f, "town finished in position {position}")
+            }
+            Reason::FinishPosition(None) => write!(f, "town not finished"),
+            Reason::Unused => write!(f, "no building on it"),
+        }
+    }
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_display() {
+        let reason = Reason::AdjacentFedBlueBuildings {
+            count: 1,
+            needed: 2,
+        };
+        assert_eq!(
+            reason.to_string(),
+            "adjacent to 1 fed blue buildings, needs 2"
+        );
+        assert_eq!(
+            Reason::LargestContiguousGroup(5).to_string(),
+            "largest contiguous group = 5"
+        );
+        let reason = Reason::AdjacentTo {
+            wanted: vec![BuildingType::Red, BuildingType::Yellow],
+            found: vec![],
+        };
+        assert_eq!(reason.to_string(), "not adjacent to any of red, yellow");
+        let reason = Reason::NotAdjacentTo {
+            forbidden: vec![BuildingType::Black, BuildingType::Green],
+            found: vec![BuildingType::Green],
+        };
+        assert_eq!(reason.to_string(), "adjacent to green");
+    }
+}
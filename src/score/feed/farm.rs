@@ -1,30 +1,48 @@
 use std::cmp;
-use std::collections::HashSet;
+use std::iter;
 
 use itertools::Itertools;
 
+use crate::board::bitboard::Bitmask;
 use crate::board::space::BuildingType;
 use crate::board::Board;
-use crate::building_config::BuildingConfig;
-use crate::score::feed::{best_fed_idxs, feedable_idxs};
+use crate::building_config::{BuildingConfig, OrangeBuilding};
+use crate::score::feed::{fed_score, feedable_idxs};
 use crate::score::ScoringContext;
 
+/// The number of buildings each farm feeds.
+pub const BUILDINGS_FED_PER_FARM: usize = 4;
+
+// -----------------------------------------------------------------------------
+/// Return the number of buildings the farms feed: as many as they can, up to
+/// every feedable building.
+fn num_fed(board: &Board, num_feedable: usize) -> usize {
+    let num_fed = cmp::min(
+        BUILDINGS_FED_PER_FARM
+            * board.count_building_type(BuildingType::Red) as usize,
+        num_feedable,
+    );
+
+    num_fed
+}
+
 // -----------------------------------------------------------------------------
+/// Every way of choosing which feedable buildings to feed. Only used to check
+/// `feed` against, as the number of combinations grows too fast to score them
+/// all on a crowded board.
+#[cfg(test)]
 fn feedable_permutations(
     board: &Board,
-    feedable_idxs: HashSet<usize>,
-) -> Vec<HashSet<usize>> {
-    let n_feedable = feedable_idxs.len();
+    feedable_idxs: &Bitmask,
+) -> Vec<Bitmask> {
+    let n_feedable = feedable_idxs.len() as usize;
     let permutations = feedable_idxs
-        .into_iter()
+        .iter()
         // Create Vec<Vec<usize>> of permutations.
-        .combinations(cmp::min(
-            4 * board.count_building_type(BuildingType::Red) as usize,
-            n_feedable,
-        ))
-        // Convert to Vec<HashSet<usize>>.
+        .combinations(num_fed(board, n_feedable))
+        // Convert to Vec<Bitmask>.
         .fold(Vec::new(), |mut perms, v| {
-            perms.push(HashSet::from_iter(v));
+            perms.push(Bitmask::from_iter(v));
             perms
         });
 
@@ -32,15 +50,91 @@ fn feedable_permutations(
 }
 
 // -----------------------------------------------------------------------------
+/// Return the feedable buildings adjacent to a temple. Whether a temple scores
+/// depends on which of them are fed together, whereas any other building adds
+/// the same points whenever it is fed.
+fn temple_dependent_idxs(
+    board: &Board,
+    building_config: &BuildingConfig,
+    feedable_idxs: &Bitmask,
+) -> Bitmask {
+    if building_config.orange() != OrangeBuilding::Temple {
+        return Bitmask::EMPTY;
+    }
+    let dependent_idxs = feedable_idxs
+        & board.spread_mask(board.building_mask(BuildingType::Orange));
+
+    dependent_idxs
+}
+
+// -----------------------------------------------------------------------------
+/// Feed the buildings that score the most. Each combination of the buildings
+/// next to temples is tried in turn, with the rest of the food going to the
+/// other buildings that add the most points, so only the combinations that
+/// can change which temples score are ever scored.
 pub fn feed(
     board: &Board,
     building_config: &BuildingConfig,
     scoring_context: &ScoringContext,
-) -> HashSet<usize> {
+) -> Bitmask {
     let feedable_idxs = feedable_idxs(board, building_config);
-    let permutations = feedable_permutations(board, feedable_idxs);
-    let fed_idxs =
-        best_fed_idxs(board, building_config, scoring_context, permutations);
+    let num_fed = num_fed(board, feedable_idxs.len() as usize);
+    let dependent_idxs =
+        temple_dependent_idxs(board, building_config, &feedable_idxs);
+
+    // Rank the other buildings by the points each adds when fed.
+    let base_score =
+        fed_score(board, building_config, scoring_context, &Bitmask::EMPTY);
+    let mut independent_idxs: Vec<(i32, usize)> = feedable_idxs
+        .difference(&dependent_idxs)
+        .iter()
+        .map(|idx| {
+            let score = fed_score(
+                board,
+                building_config,
+                scoring_context,
+                &Bitmask::single(idx),
+            );
+            (score - base_score, idx)
+        })
+        .collect();
+    independent_idxs.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+    let min_dependent = num_fed.saturating_sub(independent_idxs.len());
+    let max_dependent = cmp::min(num_fed, dependent_idxs.len() as usize);
+    let fed_idxs = (min_dependent..=max_dependent)
+        .flat_map(|n| -> Box<dyn Iterator<Item = Vec<usize>>> {
+            // `combinations(0)` yields nothing rather than a single empty
+            // combination.
+            if n == 0 {
+                Box::new(iter::once(Vec::new()))
+            } else {
+                Box::new(dependent_idxs.iter().combinations(n))
+            }
+        })
+        .map(|combination| {
+            let num_independent = num_fed - combination.len();
+            let fed_idxs: Bitmask = combination
+                .into_iter()
+                .chain(
+                    independent_idxs
+                        .iter()
+                        .take(num_independent)
+                        .map(|(_, idx)| *idx),
+                )
+                .collect();
+            fed_idxs
+        })
+        .fold((Bitmask::EMPTY, 0), |(best, max), fed_idxs| {
+            let score =
+                fed_score(board, building_config, scoring_context, &fed_idxs);
+            if score > max {
+                (fed_idxs, score)
+            } else {
+                (best, max)
+            }
+        })
+        .0;
 
     fed_idxs
 }
@@ -48,11 +142,16 @@ pub fn feed(
 // =============================================================================
 #[cfg(test)]
 mod test {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
     use super::*;
+    use crate::building_config::generate::Card;
     use crate::building_config::{
         BlackBuilding, BlueBuilding, GrayBuilding, GreenBuilding,
         MagentaBuilding, OrangeBuilding, RedBuilding, YellowBuilding,
     };
+    use crate::score::feed::best_fed_idxs;
 
     // -------------------------------------------------------------------------
     #[test]
@@ -80,21 +179,21 @@ mod test {
         board.place(21, BuildingType::Red);
         board.place(24, BuildingType::Magenta);
         let feedable = feedable_idxs(&board, &building_config);
-        let permutations = feedable_permutations(&board, feedable);
-        assert_eq!(permutations, vec![HashSet::from([0, 1, 14, 24])]);
+        let permutations = feedable_permutations(&board, &feedable);
+        assert_eq!(permutations, vec![Bitmask::from_iter([0, 1, 14, 24])]);
 
         // Add another cottage.
         board.place(25, BuildingType::Blue);
         let feedable = feedable_idxs(&board, &building_config);
-        let permutations = feedable_permutations(&board, feedable);
-        let ans = vec![
-            HashSet::from([0, 1, 14, 24]),
-            HashSet::from([0, 1, 14, 25]),
-            HashSet::from([0, 1, 24, 25]),
-            HashSet::from([0, 14, 24, 25]),
-            HashSet::from([1, 14, 24, 25]),
+        let permutations = feedable_permutations(&board, &feedable);
+        let ans = [
+            Bitmask::from_iter([0, 1, 14, 24]),
+            Bitmask::from_iter([0, 1, 14, 25]),
+            Bitmask::from_iter([0, 1, 24, 25]),
+            Bitmask::from_iter([0, 14, 24, 25]),
+            Bitmask::from_iter([1, 14, 24, 25]),
         ];
-        let eq = permutations.iter().all(|s| ans.contains(&s))
+        let eq = permutations.iter().all(|s| ans.contains(s))
             && ans.iter().all(|s| permutations.contains(s));
 
         assert!(eq);
@@ -129,36 +228,36 @@ mod test {
         // Add a farm to feed the single blue building.
         board.place(15, BuildingType::Red);
         let fed_idxs = feed(&board, &building_config, &scoring_context);
-        assert_eq!(fed_idxs, HashSet::from([0]));
+        assert_eq!(fed_idxs, Bitmask::from_iter([0]));
 
         // Add a few more blue buildings.
         board.place(1, BuildingType::Blue);
         let fed_idxs = feed(&board, &building_config, &scoring_context);
-        assert_eq!(fed_idxs, HashSet::from([0, 1]));
+        assert_eq!(fed_idxs, Bitmask::from_iter([0, 1]));
 
         board.place(2, BuildingType::Blue);
         let fed_idxs = feed(&board, &building_config, &scoring_context);
-        assert_eq!(fed_idxs, HashSet::from([0, 1, 2]));
+        assert_eq!(fed_idxs, Bitmask::from_iter([0, 1, 2]));
 
         board.place(3, BuildingType::Blue);
         let fed_idxs = feed(&board, &building_config, &scoring_context);
-        assert_eq!(fed_idxs, HashSet::from([0, 1, 2, 3]));
+        assert_eq!(fed_idxs, Bitmask::from_iter([0, 1, 2, 3]));
 
         // Five blue buildings - only four will be fed.
         board.place(4, BuildingType::Blue);
         let fed_idxs = feed(&board, &building_config, &scoring_context);
-        assert!(vec![
-            HashSet::from([0, 1, 2, 3]),
-            HashSet::from([0, 1, 2, 4]),
-            HashSet::from([0, 1, 3, 4]),
-            HashSet::from([0, 2, 3, 4]),
-            HashSet::from([1, 2, 3, 4]),
+        assert!([
+            Bitmask::from_iter([0, 1, 2, 3]),
+            Bitmask::from_iter([0, 1, 2, 4]),
+            Bitmask::from_iter([0, 1, 3, 4]),
+            Bitmask::from_iter([0, 2, 3, 4]),
+            Bitmask::from_iter([1, 2, 3, 4]),
         ]
         .contains(&fed_idxs));
         // Add another farm to feed all five.
         board.place(14, BuildingType::Red);
         let fed_idxs = feed(&board, &building_config, &scoring_context);
-        assert_eq!(fed_idxs, HashSet::from([0, 1, 2, 3, 4]));
+        assert_eq!(fed_idxs, Bitmask::from_iter([0, 1, 2, 3, 4]));
 
         // Temple, no Barret Castle.
         let building_config = BuildingConfig::new(
@@ -185,12 +284,12 @@ mod test {
         board.place(3, BuildingType::Blue);
         board.place(7, BuildingType::Blue);
         let fed_idxs = feed(&board, &building_config, &scoring_context);
-        assert_eq!(fed_idxs, HashSet::from([1, 4, 8, 13]));
+        assert_eq!(fed_idxs, Bitmask::from_iter([1, 4, 8, 13]));
 
         // Add another farm to feed all blue buildings.
         board.place(14, BuildingType::Red);
         let fed_idxs = feed(&board, &building_config, &scoring_context);
-        assert_eq!(fed_idxs, HashSet::from([1, 2, 3, 4, 7, 8, 13]));
+        assert_eq!(fed_idxs, Bitmask::from_iter([1, 2, 3, 4, 7, 8, 13]));
 
         // No temple, Barrett Castle.
         let building_config = BuildingConfig::new(
@@ -213,7 +312,7 @@ mod test {
         // Add a farm to feed one Barrett Castle.
         board.place(15, BuildingType::Red);
         let fed_idxs = feed(&board, &building_config, &scoring_context);
-        assert_eq!(fed_idxs, HashSet::from([14]));
+        assert_eq!(fed_idxs, Bitmask::from_iter([14]));
 
         // Add four blue buildings.
         board.place(0, BuildingType::Blue);
@@ -223,12 +322,12 @@ mod test {
 
         // Ensure Barret Castle is in fed buildings.
         let fed_idxs = feed(&board, &building_config, &scoring_context);
-        assert!(fed_idxs.contains(&14));
+        assert!(fed_idxs.contains(14));
 
         // Add another Barrett Castle.
         board.place(13, BuildingType::Magenta);
         let fed_idxs = feed(&board, &building_config, &scoring_context);
-        assert!(fed_idxs.contains(&13) && fed_idxs.contains(&14));
+        assert!(fed_idxs.contains(13) && fed_idxs.contains(14));
 
         // Temple and Barrett Castle.
         let building_config = BuildingConfig::new(
@@ -260,7 +359,7 @@ mod test {
         // The four cottages should be selected, as they score the most in
         // combination with the temples.
         let fed_idxs = feed(&board, &building_config, &scoring_context);
-        assert_eq!(fed_idxs, HashSet::from([1, 2, 4, 7]));
+        assert_eq!(fed_idxs, Bitmask::from_iter([1, 2, 4, 7]));
 
         // Move one of the cottages.
         board.remove(4);
@@ -270,9 +369,97 @@ mod test {
         // fed, along with the Barrett Castle.
         let fed_idxs = feed(&board, &building_config, &scoring_context);
         assert!(
-            fed_idxs.contains(&2)
-                && fed_idxs.contains(&7)
-                && fed_idxs.contains(&5),
+            fed_idxs.contains(2)
+                && fed_idxs.contains(7)
+                && fed_idxs.contains(5),
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_feed_matches_every_permutation() {
+        let scoring_context = ScoringContext::default();
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let building_types = [
+            BuildingType::Blue,
+            BuildingType::Blue,
+            BuildingType::Blue,
+            BuildingType::Orange,
+            BuildingType::Magenta,
+            BuildingType::Red,
+        ];
+        for magenta in
+            [MagentaBuilding::BarrettCastle, MagentaBuilding::SilvaForum]
+        {
+            for orange in [OrangeBuilding::Chapel, OrangeBuilding::Temple] {
+                let building_config = BuildingConfig::for_test()
+                    .with_card(Card::Black(BlackBuilding::Factory))
+                    .with_card(Card::Magenta(magenta))
+                    .with_card(Card::Orange(orange))
+                    .with_card(Card::Yellow(YellowBuilding::Theater));
+                for _ in 0..25 {
+                    let mut board = Board::new(4, 4);
+                    for idx in 0..board.elems() {
+                        if rng.gen_bool(0.75) {
+                            let i = rng.gen_range(0..building_types.len());
+                            board.place(idx, building_types[i]);
+                        }
+                    }
+                    let fed_idxs =
+                        feed(&board, &building_config, &scoring_context);
+                    let feedable = feedable_idxs(&board, &building_config);
+                    let best = best_fed_idxs(
+                        &board,
+                        &building_config,
+                        &scoring_context,
+                        feedable_permutations(&board, &feedable),
+                    );
+                    assert_eq!(
+                        fed_score(
+                            &board,
+                            &building_config,
+                            &scoring_context,
+                            &fed_idxs
+                        ),
+                        fed_score(
+                            &board,
+                            &building_config,
+                            &scoring_context,
+                            &best
+                        ),
+                        "{}",
+                        board.to_notation()
+                    );
+                }
+            }
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_feed_crowded_board() {
+        let scoring_context = ScoringContext::default();
+        let building_config = BuildingConfig::for_test()
+            .with_card(Card::Black(BlackBuilding::Factory))
+            .with_card(Card::Magenta(MagentaBuilding::BarrettCastle))
+            .with_card(Card::Orange(OrangeBuilding::Temple))
+            .with_card(Card::Yellow(YellowBuilding::Theater));
+        // 30 cottages and 4 farms would be over 145 million combinations.
+        let mut board = Board::new(6, 6);
+        for idx in 0..board.elems() {
+            board.place(idx, BuildingType::Blue);
+        }
+        for idx in [0, 5, 30, 35] {
+            board.place(idx, BuildingType::Red);
+        }
+        for idx in [14, 21] {
+            board.place(idx, BuildingType::Orange);
+        }
+        let fed_idxs = feed(&board, &building_config, &scoring_context);
+        assert_eq!(fed_idxs.len(), 16);
+        assert_eq!(
+            fed_score(&board, &building_config, &scoring_context, &fed_idxs),
+            16 * 3 + 2 * 4
         );
     }
 }
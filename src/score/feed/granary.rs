@@ -1,34 +1,32 @@
-use std::collections::HashSet;
-
+use crate::board::bitboard::Bitmask;
 use crate::board::space::BuildingType;
 use crate::board::Board;
 use crate::building_config::BuildingConfig;
 use crate::score::feed::feedable_idxs;
 
 // -----------------------------------------------------------------------------
-pub fn feed(board: &Board, building_config: &BuildingConfig) -> HashSet<usize> {
-    let (fed_rows, fed_cols) = board.spaces().iter().enumerate().fold(
-        (HashSet::new(), HashSet::new()),
-        |(mut fed_rows, mut fed_cols), (idx, space)| {
-            if space.building_type_eq(BuildingType::Red) {
-                fed_rows.insert(board.row(idx));
-                fed_cols.insert(board.col(idx));
-            }
-            (fed_rows, fed_cols)
-        },
-    );
+/// Return the granaries in the row or column of space #idx.
+fn feeder_mask(board: &Board, idx: usize) -> Bitmask {
+    let row_and_col =
+        board.row_mask(board.row(idx)) | board.col_mask(board.col(idx));
+    let feeder_mask = row_and_col & board.building_mask(BuildingType::Red);
+
+    feeder_mask
+}
+
+// -----------------------------------------------------------------------------
+/// Return the indices of the granaries that feed the space at `idx`, which are
+/// those in its row or column, in index order.
+pub fn feeders(board: &Board, idx: usize) -> Vec<usize> {
+    feeder_mask(board, idx).iter().collect()
+}
 
-    let fed_idxs = feedable_idxs(board, building_config).into_iter().fold(
-        HashSet::new(),
-        |mut fed_idxs, idx| {
-            if fed_rows.contains(&board.row(idx))
-                || fed_cols.contains(&board.col(idx))
-            {
-                fed_idxs.insert(idx);
-            }
-            fed_idxs
-        },
-    );
+// -----------------------------------------------------------------------------
+pub fn feed(board: &Board, building_config: &BuildingConfig) -> Bitmask {
+    let fed_idxs = feedable_idxs(board, building_config)
+        .iter()
+        .filter(|idx| !feeder_mask(board, *idx).is_empty())
+        .collect();
 
     fed_idxs
 }
@@ -64,16 +62,19 @@ mod test {
         assert!(feed(&board, &building_config).is_empty());
 
         board.place(5, BuildingType::Red);
-        assert_eq!(feed(&board, &building_config), HashSet::from([1]));
+        assert_eq!(feed(&board, &building_config), Bitmask::from_iter([1]));
 
         board.place(4, BuildingType::Blue);
         board.place(6, BuildingType::Blue);
         board.place(9, BuildingType::Magenta);
         board.place(12, BuildingType::Blue);
-        assert_eq!(feed(&board, &building_config), HashSet::from([1, 4, 6]));
+        assert_eq!(
+            feed(&board, &building_config),
+            Bitmask::from_iter([1, 4, 6])
+        );
 
         board.place(13, BuildingType::Red);
-        let ans = HashSet::from([1, 4, 6, 12]);
+        let ans = Bitmask::from_iter([1, 4, 6, 12]);
         assert_eq!(feed(&board, &building_config), ans);
 
         // With Barrett Castle.
@@ -87,7 +88,7 @@ mod test {
             RedBuilding::Granary,
             YellowBuilding::Theater,
         );
-        let ans = HashSet::from([1, 4, 6, 9, 12]);
+        let ans = Bitmask::from_iter([1, 4, 6, 9, 12]);
         assert_eq!(feed(&board, &building_config), ans);
     }
 }
@@ -1,39 +1,46 @@
-use std::collections::HashSet;
+use std::cmp;
 
+#[cfg(test)]
 use itertools::Itertools;
 
+use crate::board::bitboard::Bitmask;
 use crate::board::space::BuildingType;
 use crate::board::Board;
-use crate::building_config::{BuildingConfig, MagentaBuilding};
-use crate::score::feed::best_fed_idxs;
+use crate::building_config::{BuildingConfig, OrangeBuilding};
+use crate::score::feed::{fed_score, feedable_idxs};
 use crate::score::ScoringContext;
 
 // -----------------------------------------------------------------------------
+/// Return the contiguous groups of feedable buildings, each of which a
+/// greenhouse feeds as a whole.
+pub fn feedable_groups(
+    board: &Board,
+    building_config: &BuildingConfig,
+) -> Vec<Bitmask> {
+    let groups =
+        board.contiguous_group_masks(&feedable_idxs(board, building_config));
+
+    groups
+}
+
+// -----------------------------------------------------------------------------
+/// Every way of choosing which groups to feed. Only used to check `feed`
+/// against, as the number of combinations grows too fast to score them all on
+/// a large board.
+#[cfg(test)]
 fn feedable_permutations(
     board: &Board,
     building_config: &BuildingConfig,
-) -> Vec<HashSet<usize>> {
-    let building_types =
-        if building_config.magenta() == MagentaBuilding::BarrettCastle {
-            HashSet::from([BuildingType::Blue, BuildingType::Magenta])
-        } else {
-            HashSet::from([BuildingType::Blue])
-        };
-
-    let permutations = board
-        .contiguous_groups(&building_types)
+) -> Vec<Bitmask> {
+    let permutations = feedable_groups(board, building_config)
         .into_iter()
         .combinations(board.count_building_type(BuildingType::Red) as usize)
         .fold(Vec::new(), |mut permutations, groups| {
             // A single permutation consists of N contiguous groups of feedable
             // buildings, where N is the number of greenhouses on the board.
-            let permutation =
-                groups
-                    .iter()
-                    .fold(HashSet::new(), |mut permutation, group| {
-                        permutation.extend(group);
-                        permutation
-                    });
+            let permutation = groups
+                .into_iter()
+                .fold(Bitmask::EMPTY, |permutation, group| permutation | group);
             permutations.push(permutation);
             permutations
         });
@@ -42,14 +49,123 @@ fn feedable_permutations(
 }
 
 // -----------------------------------------------------------------------------
+/// Return the most points feeding `group` can add to any other groups that
+/// are fed: the points it scores when fed alone, and the points of each
+/// temple next to it, which it may be the one to make score or not.
+fn upper_bound(
+    board: &Board,
+    building_config: &BuildingConfig,
+    scoring_context: &ScoringContext,
+    group: &Bitmask,
+    base_score: i32,
+) -> i32 {
+    let score = fed_score(board, building_config, scoring_context, group);
+    let num_adjacent_temples = if building_config.orange()
+        == OrangeBuilding::Temple
+    {
+        (board.spread_mask(group) & board.building_mask(BuildingType::Orange))
+            .len()
+    } else {
+        0
+    };
+    let upper_bound = score - base_score
+        + num_adjacent_temples as i32 * scoring_context.points_per_temple.abs();
+
+    upper_bound
+}
+
+// =============================================================================
+/// A branch and bound search for the groups that score the most when fed.
+struct Search<'a> {
+    board: &'a Board,
+    building_config: &'a BuildingConfig,
+    scoring_context: &'a ScoringContext,
+    /// Each group with the upper bound on the points feeding it adds, from the
+    /// highest bound to the lowest.
+    groups: Vec<(i32, Bitmask)>,
+    best_fed_idxs: Bitmask,
+    best_score: i32,
+}
+
+impl Search<'_> {
+    /// Feed `num_left` more groups from the `i`th group on, besides those
+    /// already in `fed_idxs`, keeping the best found so far.
+    fn branch(&mut self, i: usize, fed_idxs: &Bitmask, num_left: usize) {
+        let score = fed_score(
+            self.board,
+            self.building_config,
+            self.scoring_context,
+            fed_idxs,
+        );
+        if num_left == 0 {
+            if score > self.best_score {
+                self.best_score = score;
+                self.best_fed_idxs = fed_idxs.clone();
+            }
+            return;
+        }
+
+        // The groups are sorted by their bounds, so the next `num_left` have
+        // the most to add.
+        let bound = score
+            + self.groups[i..i + num_left]
+                .iter()
+                .map(|(upper_bound, _)| upper_bound)
+                .sum::<i32>();
+        if bound <= self.best_score {
+            return;
+        }
+
+        // Feed the group, then try the rest without it.
+        let with_group = fed_idxs | &self.groups[i].1;
+        self.branch(i + 1, &with_group, num_left - 1);
+        if self.groups.len() - (i + 1) >= num_left {
+            self.branch(i + 1, fed_idxs, num_left);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+/// Feed the groups that score the most, one per greenhouse, searching the
+/// groups with the most to add first and giving up on any choice that cannot
+/// beat the best found so far.
 pub fn feed(
     board: &Board,
     building_config: &BuildingConfig,
     scoring_context: &ScoringContext,
-) -> HashSet<usize> {
-    let permutations = feedable_permutations(board, building_config);
-    let fed_buildings =
-        best_fed_idxs(board, building_config, scoring_context, permutations);
+) -> Bitmask {
+    let base_score =
+        fed_score(board, building_config, scoring_context, &Bitmask::EMPTY);
+    let mut groups: Vec<(i32, Bitmask)> =
+        feedable_groups(board, building_config)
+            .into_iter()
+            .map(|group| {
+                let upper_bound = upper_bound(
+                    board,
+                    building_config,
+                    scoring_context,
+                    &group,
+                    base_score,
+                );
+                (upper_bound, group)
+            })
+            .collect();
+    groups.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.first().cmp(&b.1.first())));
+    let num_fed = cmp::min(
+        board.count_building_type(BuildingType::Red) as usize,
+        groups.len(),
+    );
+
+    let mut search = Search {
+        board,
+        building_config,
+        scoring_context,
+        groups,
+        best_fed_idxs: Bitmask::EMPTY,
+        best_score: 0,
+    };
+    search.branch(0, &Bitmask::EMPTY, num_fed);
+    let fed_buildings = search.best_fed_idxs;
 
     fed_buildings
 }
@@ -57,12 +173,16 @@ pub fn feed(
 // =============================================================================
 #[cfg(test)]
 mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
     use super::*;
+    use crate::building_config::generate::Card;
     use crate::building_config::{
         BlackBuilding, BlueBuilding, GrayBuilding, GreenBuilding,
         MagentaBuilding, OrangeBuilding, RedBuilding, YellowBuilding,
     };
-    use crate::utils::vec_hashset_eq;
+    use crate::score::feed::best_fed_idxs;
 
     // -------------------------------------------------------------------------
     #[test]
@@ -84,35 +204,35 @@ mod tests {
 
         board.place(0, BuildingType::Blue);
         let permutations = feedable_permutations(&board, &building_config);
-        assert_eq!(permutations, vec![HashSet::new()]);
+        assert_eq!(permutations, vec![Bitmask::EMPTY]);
 
         board.place(15, BuildingType::Red);
         let permutations = feedable_permutations(&board, &building_config);
-        assert_eq!(permutations, vec![HashSet::from([0])]);
+        assert_eq!(permutations, vec![Bitmask::from_iter([0])]);
 
         board.place(1, BuildingType::Blue);
         let permutations = feedable_permutations(&board, &building_config);
-        assert_eq!(permutations, vec![HashSet::from([0, 1])]);
+        assert_eq!(permutations, vec![Bitmask::from_iter([0, 1])]);
 
         board.place(3, BuildingType::Blue);
         let permutations = feedable_permutations(&board, &building_config);
-        let ans = vec![HashSet::from([0, 1]), HashSet::from([3])];
-        assert!(vec_hashset_eq(&permutations, &ans));
+        let ans = vec![Bitmask::from_iter([0, 1]), Bitmask::from_iter([3])];
+        assert_eq!(permutations, ans);
 
         board.place(7, BuildingType::Blue);
         let permutations = feedable_permutations(&board, &building_config);
-        let ans = vec![HashSet::from([0, 1]), HashSet::from([3, 7])];
-        assert!(vec_hashset_eq(&permutations, &ans));
+        let ans = vec![Bitmask::from_iter([0, 1]), Bitmask::from_iter([3, 7])];
+        assert_eq!(permutations, ans);
 
         board.place(2, BuildingType::Blue);
         let permutations = feedable_permutations(&board, &building_config);
-        let ans = vec![HashSet::from([0, 1, 2, 3, 7])];
-        assert!(vec_hashset_eq(&permutations, &ans));
+        let ans = vec![Bitmask::from_iter([0, 1, 2, 3, 7])];
+        assert_eq!(permutations, ans);
 
         board.place(12, BuildingType::Magenta);
         let permutations = feedable_permutations(&board, &building_config);
-        let ans = vec![HashSet::from([0, 1, 2, 3, 7])];
-        assert!(vec_hashset_eq(&permutations, &ans));
+        let ans = vec![Bitmask::from_iter([0, 1, 2, 3, 7])];
+        assert_eq!(permutations, ans);
 
         // With Barrett Castle.
         let building_config = BuildingConfig::new(
@@ -127,23 +247,29 @@ mod tests {
         );
 
         let permutations = feedable_permutations(&board, &building_config);
-        let ans = vec![HashSet::from([0, 1, 2, 3, 7]), HashSet::from([12])];
-        assert!(vec_hashset_eq(&permutations, &ans));
+        let ans = vec![
+            Bitmask::from_iter([0, 1, 2, 3, 7]),
+            Bitmask::from_iter([12]),
+        ];
+        assert_eq!(permutations, ans);
 
         board.place(13, BuildingType::Blue);
         let permutations = feedable_permutations(&board, &building_config);
-        let ans = vec![HashSet::from([0, 1, 2, 3, 7]), HashSet::from([12, 13])];
-        assert!(vec_hashset_eq(&permutations, &ans));
+        let ans = vec![
+            Bitmask::from_iter([0, 1, 2, 3, 7]),
+            Bitmask::from_iter([12, 13]),
+        ];
+        assert_eq!(permutations, ans);
 
         // Add second greenhouse - both contiguous groups can be fed now, so
         // they form one permutation.
         board.place(14, BuildingType::Red);
         let permutations = feedable_permutations(&board, &building_config);
-        let ans = vec![HashSet::from([0, 1, 2, 3, 7, 12, 13])];
+        let ans = vec![Bitmask::from_iter([0, 1, 2, 3, 7, 12, 13])];
         println!("permutations: {:?}", permutations);
         println!("ans: {:?}", ans);
 
-        assert!(vec_hashset_eq(&permutations, &ans));
+        assert_eq!(permutations, ans);
     }
 
     // -------------------------------------------------------------------------
@@ -190,7 +316,7 @@ mod tests {
         // Now place the greenhouse.
         board.place(3, BuildingType::Red);
 
-        let ans = HashSet::from([4, 5, 10, 11]);
+        let ans = Bitmask::from_iter([4, 5, 10, 11]);
         assert_eq!(feed(&board, &building_config, &scoring_context), ans);
 
         // With Temple, without Barrett Castle.
@@ -205,7 +331,7 @@ mod tests {
             YellowBuilding::Theater,
         );
 
-        let ans = HashSet::from([1, 6, 7]);
+        let ans = Bitmask::from_iter([1, 6, 7]);
         assert_eq!(feed(&board, &building_config, &scoring_context), ans);
 
         // Without Temple, with Barrett Castle.
@@ -220,7 +346,7 @@ mod tests {
             YellowBuilding::Theater,
         );
 
-        let ans = HashSet::from([28, 29, 34, 35]);
+        let ans = Bitmask::from_iter([28, 29, 34, 35]);
         assert_eq!(feed(&board, &building_config, &scoring_context), ans);
 
         // With Temple and Barrett Castle.
@@ -235,7 +361,114 @@ mod tests {
             YellowBuilding::Theater,
         );
 
-        let ans = HashSet::from([31]);
+        let ans = Bitmask::from_iter([31]);
         assert_eq!(feed(&board, &building_config, &scoring_context), ans);
     }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_feed_matches_every_permutation() {
+        let scoring_context = ScoringContext::default();
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let building_types = [
+            BuildingType::Blue,
+            BuildingType::Blue,
+            BuildingType::Orange,
+            BuildingType::Magenta,
+            BuildingType::Red,
+        ];
+        for magenta in
+            [MagentaBuilding::BarrettCastle, MagentaBuilding::SilvaForum]
+        {
+            for orange in [OrangeBuilding::Chapel, OrangeBuilding::Temple] {
+                let building_config = BuildingConfig::for_test()
+                    .with_card(Card::Black(BlackBuilding::Factory))
+                    .with_card(Card::Magenta(magenta))
+                    .with_card(Card::Orange(orange))
+                    .with_card(Card::Red(RedBuilding::Greenhouse))
+                    .with_card(Card::Yellow(YellowBuilding::Theater));
+                for _ in 0..25 {
+                    let mut board = Board::new(5, 5);
+                    for idx in 0..board.elems() {
+                        if rng.gen_bool(0.6) {
+                            let i = rng.gen_range(0..building_types.len());
+                            board.place(idx, building_types[i]);
+                        }
+                    }
+                    let fed_idxs =
+                        feed(&board, &building_config, &scoring_context);
+                    let best = best_fed_idxs(
+                        &board,
+                        &building_config,
+                        &scoring_context,
+                        feedable_permutations(&board, &building_config),
+                    );
+                    // Every permutation is empty when there are more
+                    // greenhouses than groups.
+                    if board.count_building_type(BuildingType::Red) as usize
+                        > feedable_groups(&board, &building_config).len()
+                    {
+                        continue;
+                    }
+                    assert_eq!(
+                        fed_score(
+                            &board,
+                            &building_config,
+                            &scoring_context,
+                            &fed_idxs
+                        ),
+                        fed_score(
+                            &board,
+                            &building_config,
+                            &scoring_context,
+                            &best
+                        ),
+                        "{}",
+                        board.to_notation()
+                    );
+                }
+            }
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_feed_many_groups() {
+        let scoring_context = ScoringContext::default();
+        let building_config = BuildingConfig::for_test()
+            .with_card(Card::Black(BlackBuilding::Factory))
+            .with_card(Card::Magenta(MagentaBuilding::BarrettCastle))
+            .with_card(Card::Orange(OrangeBuilding::Temple))
+            .with_card(Card::Red(RedBuilding::Greenhouse))
+            .with_card(Card::Yellow(YellowBuilding::Theater));
+
+        // A checkerboard of 32 single cottages, fed by 6 greenhouses, would
+        // be over 900,000 combinations.
+        let mut board = Board::new(8, 8);
+        for row in 0..board.rows() {
+            for col in (row % 2..board.cols()).step_by(2) {
+                board.place(board.idx(row, col), BuildingType::Blue);
+            }
+        }
+        for idx in [7, 56] {
+            board.place(idx, BuildingType::Orange);
+        }
+        for idx in [1, 3, 5, 58, 60, 62] {
+            board.place(idx, BuildingType::Red);
+        }
+        let fed_idxs = feed(&board, &building_config, &scoring_context);
+        assert_eq!(fed_idxs.len(), 6);
+        assert_eq!(
+            fed_score(&board, &building_config, &scoring_context, &fed_idxs),
+            6 * 3 + 2 * 4
+        );
+
+        // With more greenhouses than groups, every group is fed.
+        let mut board = Board::new(4, 4);
+        board.place(0, BuildingType::Blue);
+        board.place(14, BuildingType::Red);
+        board.place(15, BuildingType::Red);
+        let fed_idxs = feed(&board, &building_config, &scoring_context);
+        assert_eq!(fed_idxs, Bitmask::from_iter([0]));
+    }
 }
@@ -1,6 +1,9 @@
-use std::collections::HashSet;
+use std::collections::HashMap;
 
-use crate::board::space::{BuildingType, Space};
+use serde::{Deserialize, Serialize};
+
+use crate::board::bitboard::Bitmask;
+use crate::board::space::BuildingType;
 use crate::board::Board;
 use crate::building_config::{BuildingConfig, MagentaBuilding, RedBuilding};
 use crate::score::{blue, magenta, orange, ScoringContext};
@@ -10,69 +13,100 @@ pub mod granary;
 pub mod greenhouse;
 pub mod orchard;
 
+// =============================================================================
+/// The red building, or buildings, that fed a building.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Feeder {
+    /// One of the buildings fed by the farm at this index.
+    Farm(usize),
+    /// Fed by the granaries at these indices.
+    Granaries(Vec<usize>),
+    /// Fed by the orchards at these indices.
+    Orchards(Vec<usize>),
+    /// Fed along with the rest of `group` by the greenhouse at `greenhouse`.
+    Greenhouse {
+        greenhouse: usize,
+        group: Vec<usize>,
+    },
+}
+
+// =============================================================================
+/// Which red building fed each fed building in a town, and which feedable
+/// buildings went unfed.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FeedingReport {
+    fed: HashMap<usize, Feeder>,
+    unfed: Vec<usize>,
+}
+
+impl FeedingReport {
+    /// Return the feeder of each fed Cottage or Barrett Castle, by index.
+    pub fn fed(&self) -> &HashMap<usize, Feeder> {
+        &self.fed
+    }
+
+    /// Return the indices of the feedable buildings left unfed, in order.
+    pub fn unfed(&self) -> &[usize] {
+        &self.unfed
+    }
+}
+
 // -----------------------------------------------------------------------------
-fn is_feedable(building_config: &BuildingConfig, space: &Space) -> bool {
-    let is_feedable = space.building_type_eq(BuildingType::Blue)
-        || (space.building_type_eq(BuildingType::Magenta)
-            && building_config.magenta() == MagentaBuilding::BarrettCastle);
+fn feedable_idxs(board: &Board, building_config: &BuildingConfig) -> Bitmask {
+    let feedable_idxs =
+        if building_config.magenta() == MagentaBuilding::BarrettCastle {
+            board.building_mask(BuildingType::Blue)
+                | board.building_mask(BuildingType::Magenta)
+        } else {
+            board.building_mask(BuildingType::Blue).clone()
+        };
 
-    is_feedable
+    feedable_idxs
 }
 
 // -----------------------------------------------------------------------------
-fn feedable_idxs(
+/// Return the points the fed buildings, and those that depend on them, score
+/// when the buildings at `fed_idxs` are fed.
+fn fed_score(
     board: &Board,
     building_config: &BuildingConfig,
-) -> HashSet<usize> {
-    let feedable_idxs = board.spaces().iter().enumerate().fold(
-        HashSet::new(),
-        |mut s, (idx, space)| {
-            if is_feedable(building_config, space) {
-                s.insert(idx);
-            }
-            s
-        },
-    );
+    scoring_context: &ScoringContext,
+    fed_idxs: &Bitmask,
+) -> i32 {
+    let score = blue::score(board, building_config, scoring_context, fed_idxs)
+        .values()
+        .sum::<i32>()
+        + orange::score(board, building_config, scoring_context, fed_idxs)
+            .values()
+            .sum::<i32>()
+        + magenta::score(board, building_config, scoring_context, fed_idxs)
+            .values()
+            .sum::<i32>();
 
-    feedable_idxs
+    score
 }
 
 // -----------------------------------------------------------------------------
+/// Return the permutation of fed buildings that scores the most, by scoring
+/// every one. Only used to check the farm and greenhouse searches against.
+#[cfg(test)]
 fn best_fed_idxs(
     board: &Board,
     building_config: &BuildingConfig,
     scoring_context: &ScoringContext,
-    permutations: Vec<HashSet<usize>>,
-) -> HashSet<usize> {
+    permutations: Vec<Bitmask>,
+) -> Bitmask {
     let best_fed_idxs = permutations
-        .iter()
-        .fold((HashSet::new(), 0), |(best, max), permutation| {
-            let score = blue::score(
+        .into_iter()
+        .fold((Bitmask::EMPTY, 0), |(best, max), permutation| {
+            let score = fed_score(
                 board,
                 building_config,
                 scoring_context,
-                permutation,
-            )
-            .values()
-            .sum::<i32>()
-                + orange::score(
-                    board,
-                    building_config,
-                    scoring_context,
-                    permutation,
-                )
-                .values()
-                .sum::<i32>()
-                + magenta::score(
-                    board,
-                    building_config,
-                    scoring_context,
-                    permutation,
-                )
-                .values()
-                .sum::<i32>() as i32;
+                &permutation,
+            );
             if score > max {
-                (permutation.clone(), score)
+                (permutation, score)
             } else {
                 (best, max)
             }
@@ -87,7 +121,7 @@ pub fn feed(
     board: &Board,
     building_config: &BuildingConfig,
     scoring_context: &ScoringContext,
-) -> HashSet<usize> {
+) -> Bitmask {
     let fed_idxs = match building_config.red() {
         RedBuilding::Farm => {
             farm::feed(board, building_config, scoring_context)
@@ -102,10 +136,74 @@ pub fn feed(
     fed_idxs
 }
 
+// -----------------------------------------------------------------------------
+/// Feed the town as `feed` does, and report which red building fed each
+/// building. Farms are given the buildings they feed in index order, as are
+/// greenhouses the groups they feed.
+pub fn report(
+    board: &Board,
+    building_config: &BuildingConfig,
+    scoring_context: &ScoringContext,
+) -> FeedingReport {
+    let fed_idxs = feed(board, building_config, scoring_context);
+    let sorted_fed_idxs: Vec<usize> = fed_idxs.iter().collect();
+    let red_idxs: Vec<usize> =
+        board.building_mask(BuildingType::Red).iter().collect();
+
+    let fed = match building_config.red() {
+        RedBuilding::Farm => sorted_fed_idxs
+            .chunks(farm::BUILDINGS_FED_PER_FARM)
+            .zip(red_idxs)
+            .flat_map(|(idxs, farm)| {
+                idxs.iter().map(move |idx| (*idx, Feeder::Farm(farm)))
+            })
+            .collect(),
+        RedBuilding::Granary => sorted_fed_idxs
+            .into_iter()
+            .map(|idx| (idx, Feeder::Granaries(granary::feeders(board, idx))))
+            .collect(),
+        RedBuilding::Greenhouse => {
+            let mut groups: Vec<Vec<usize>> =
+                greenhouse::feedable_groups(board, building_config)
+                    .into_iter()
+                    .filter(|group| group.difference(&fed_idxs).is_empty())
+                    .map(|group| group.iter().collect())
+                    .collect();
+            groups.sort();
+            groups
+                .into_iter()
+                .zip(red_idxs)
+                .flat_map(|(group, greenhouse)| {
+                    group.clone().into_iter().map(move |idx| {
+                        let feeder = Feeder::Greenhouse {
+                            greenhouse,
+                            group: group.clone(),
+                        };
+                        (idx, feeder)
+                    })
+                })
+                .collect()
+        }
+        RedBuilding::Orchard => sorted_fed_idxs
+            .into_iter()
+            .map(|idx| (idx, Feeder::Orchards(orchard::feeders(board, idx))))
+            .collect(),
+    };
+    let unfed = feedable_idxs(board, building_config)
+        .difference(&fed_idxs)
+        .iter()
+        .collect();
+
+    let report = FeedingReport { fed, unfed };
+
+    report
+}
+
 // =============================================================================
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::building_config::generate::Card;
     use crate::building_config::{
         BlackBuilding, BlueBuilding, GrayBuilding, GreenBuilding,
         MagentaBuilding, OrangeBuilding, RedBuilding, YellowBuilding,
@@ -138,7 +236,7 @@ mod test {
         board.place(24, BuildingType::Magenta);
         assert_eq!(
             feedable_idxs(&board, &building_config),
-            HashSet::from([0, 7, 14])
+            Bitmask::from_iter([0, 7, 14])
         );
 
         // Map with Barrett Castle.
@@ -154,7 +252,74 @@ mod test {
         );
         assert_eq!(
             feedable_idxs(&board, &building_config),
-            HashSet::from([0, 7, 14, 24])
+            Bitmask::from_iter([0, 7, 14, 24])
         );
     }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_report() {
+        let scoring_context = ScoringContext::default();
+        let building_config = BuildingConfig::for_test()
+            .with_card(Card::Black(BlackBuilding::Factory))
+            .with_card(Card::Yellow(YellowBuilding::Theater));
+        let board = Board::from_notation(
+            "
+            B B B .
+            B B . .
+            . . . R
+            . . . .
+            ",
+        )
+        .unwrap();
+        let feeding = report(&board, &building_config, &scoring_context);
+        assert_eq!(feeding.fed().len(), 4);
+        assert!(feeding
+            .fed()
+            .values()
+            .all(|feeder| *feeder == Feeder::Farm(11)));
+        assert_eq!(feeding.unfed().len(), 1);
+
+        // Granaries feed their row and column.
+        let granary = BuildingConfig::for_test()
+            .with_card(Card::Black(BlackBuilding::Factory))
+            .with_card(Card::Red(RedBuilding::Granary))
+            .with_card(Card::Yellow(YellowBuilding::Theater));
+        let board = Board::from_notation(
+            "
+            B . . R
+            . B . .
+            . . . .
+            . . . B
+            ",
+        )
+        .unwrap();
+        let feeding = report(&board, &granary, &scoring_context);
+        assert_eq!(feeding.fed()[&0], Feeder::Granaries(vec![3]));
+        assert_eq!(feeding.fed()[&15], Feeder::Granaries(vec![3]));
+        assert_eq!(feeding.unfed(), [5]);
+
+        // Greenhouses feed a whole contiguous group.
+        let greenhouse = BuildingConfig::for_test()
+            .with_card(Card::Black(BlackBuilding::Factory))
+            .with_card(Card::Red(RedBuilding::Greenhouse))
+            .with_card(Card::Yellow(YellowBuilding::Theater));
+        let board = Board::from_notation(
+            "
+            B B . R
+            . . . .
+            . . . .
+            B . . .
+            ",
+        )
+        .unwrap();
+        let feeding = report(&board, &greenhouse, &scoring_context);
+        let feeder = Feeder::Greenhouse {
+            greenhouse: 3,
+            group: vec![0, 1],
+        };
+        assert_eq!(feeding.fed()[&0], feeder);
+        assert_eq!(feeding.fed()[&1], feeder);
+        assert_eq!(feeding.unfed(), [12]);
+    }
 }
@@ -1,24 +1,28 @@
-use std::collections::HashSet;
-
+use crate::board::bitboard::Bitmask;
 use crate::board::space::BuildingType;
 use crate::board::Board;
 use crate::building_config::BuildingConfig;
 use crate::score::feed::feedable_idxs;
 
 // -----------------------------------------------------------------------------
-pub fn feed(board: &Board, building_config: &BuildingConfig) -> HashSet<usize> {
-    let fed_idxs = feedable_idxs(board, building_config).into_iter().fold(
-        HashSet::new(),
-        |mut fed_idxs, idx| {
-            if board
-                .unique_surrounding_building_types(idx)
-                .contains(&BuildingType::Red)
-            {
-                fed_idxs.insert(idx);
-            }
-            fed_idxs
-        },
-    );
+/// Return the orchards surrounding space #idx.
+fn feeder_mask(board: &Board, idx: usize) -> Bitmask {
+    board.surrounding_mask(idx) & board.building_mask(BuildingType::Red)
+}
+
+// -----------------------------------------------------------------------------
+/// Return the indices of the orchards that feed the space at `idx`, which are
+/// those surrounding it, in index order.
+pub fn feeders(board: &Board, idx: usize) -> Vec<usize> {
+    feeder_mask(board, idx).iter().collect()
+}
+
+// -----------------------------------------------------------------------------
+pub fn feed(board: &Board, building_config: &BuildingConfig) -> Bitmask {
+    let fed_idxs = feedable_idxs(board, building_config)
+        .iter()
+        .filter(|idx| !feeder_mask(board, *idx).is_empty())
+        .collect();
 
     fed_idxs
 }
@@ -53,7 +57,7 @@ mod test {
         assert!(feed(&board, &building_config).is_empty());
 
         board.place(5, BuildingType::Red);
-        assert_eq!(feed(&board, &building_config), HashSet::from([1]));
+        assert_eq!(feed(&board, &building_config), Bitmask::from_iter([1]));
 
         board.place(0, BuildingType::Blue);
         board.place(2, BuildingType::Blue);
@@ -64,11 +68,11 @@ mod test {
         board.place(10, BuildingType::Blue);
         board.place(11, BuildingType::Blue);
 
-        let ans = HashSet::from([0, 1, 2, 4, 8, 9, 10]);
+        let ans = Bitmask::from_iter([0, 1, 2, 4, 8, 9, 10]);
         assert_eq!(feed(&board, &building_config), ans);
 
         board.place(14, BuildingType::Red);
-        let ans = HashSet::from([0, 1, 2, 4, 8, 9, 10, 11]);
+        let ans = Bitmask::from_iter([0, 1, 2, 4, 8, 9, 10, 11]);
         assert_eq!(feed(&board, &building_config), ans);
 
         // With Barrett Castle.
@@ -83,7 +87,7 @@ mod test {
             YellowBuilding::Theater,
         );
 
-        let ans = HashSet::from([0, 1, 2, 4, 6, 8, 9, 10, 11]);
+        let ans = Bitmask::from_iter([0, 1, 2, 4, 6, 8, 9, 10, 11]);
         assert_eq!(feed(&board, &building_config), ans);
     }
 }
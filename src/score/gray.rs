@@ -1,25 +1,31 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::board::bitboard::Bitmask;
 use crate::board::space::BuildingType;
 use crate::board::Board;
 use crate::building_config::{BuildingConfig, GrayBuilding};
+use crate::score::explain::Reason;
 use crate::score::{
-    score_if_adjacent_to, score_if_in_idx_set, score_per_each, ScoringContext,
+    explain_by_adjacency, explain_per_each, score_if_adjacent_to,
+    score_if_in_mask, score_per_each, ScoringContext,
 };
 
+/// The building types a Millstone must be adjacent to in order to score.
+const MILLSTONE_ADJACENT_TYPES: [BuildingType; 2] =
+    [BuildingType::Red, BuildingType::Yellow];
+
 // -----------------------------------------------------------------------------
 fn score_fountains(
     board: &Board,
     scoring_context: &ScoringContext,
 ) -> HashMap<usize, i32> {
-    let scoring_idxs: HashSet<usize> = board
-        .contiguous_groups(&HashSet::from([BuildingType::Gray]))
+    let scoring_idxs = board
+        .contiguous_group_masks(board.building_mask(BuildingType::Gray))
         .into_iter()
         .filter(|group| group.len() > 1)
-        .flatten()
-        .collect();
+        .fold(Bitmask::EMPTY, |scoring_idxs, group| scoring_idxs | group);
 
-    let scores = score_if_in_idx_set(
+    let scores = score_if_in_mask(
         board,
         &scoring_idxs,
         BuildingType::Gray,
@@ -37,35 +43,34 @@ fn score_millstones(
     let scores = score_if_adjacent_to(
         board,
         BuildingType::Gray,
-        &HashSet::from([BuildingType::Red, BuildingType::Yellow]),
+        &HashSet::from(MILLSTONE_ADJACENT_TYPES),
         scoring_context.points_per_millstone,
     );
 
     scores
 }
 
+// -----------------------------------------------------------------------------
+/// Return the number of blue buildings adjacent to the well at `idx`.
+fn num_adjacent_blue_buildings(board: &Board, idx: usize) -> u32 {
+    (board.adjacent_mask(idx) & board.building_mask(BuildingType::Blue)).len()
+}
+
 // -----------------------------------------------------------------------------
 fn score_wells(
     board: &Board,
     scoring_context: &ScoringContext,
 ) -> HashMap<usize, i32> {
-    let scores = board.spaces().iter().enumerate().fold(
-        HashMap::new(),
-        |mut m, (idx, space)| {
-            if space.building_type_eq(BuildingType::Gray) {
-                let count = board.count_adjacent_buildings(
-                    idx,
-                    &HashSet::from([BuildingType::Blue]),
-                );
-                let points = count as i32
-                    * scoring_context
-                        .points_per_adjacent_blue_building_for_wells;
-
-                m.insert(idx, points);
-            }
-            m
-        },
-    );
+    let scores = board
+        .building_mask(BuildingType::Gray)
+        .iter()
+        .map(|idx| {
+            let count = num_adjacent_blue_buildings(board, idx);
+            let points = count as i32
+                * scoring_context.points_per_adjacent_blue_building_for_wells;
+            (idx, points)
+        })
+        .collect();
 
     scores
 }
@@ -90,6 +95,46 @@ pub fn score(
     scores
 }
 
+// -----------------------------------------------------------------------------
+pub fn explain(
+    board: &Board,
+    building_config: &BuildingConfig,
+) -> HashMap<usize, Reason> {
+    let reasons = match building_config.gray() {
+        GrayBuilding::Fountain => {
+            let groups = board.contiguous_group_masks(
+                board.building_mask(BuildingType::Gray),
+            );
+            explain_per_each(board, BuildingType::Gray, |idx| {
+                let size = groups
+                    .iter()
+                    .find(|group| group.contains(idx))
+                    .map(|group| group.len() as usize)
+                    .unwrap_or(1);
+                Reason::ContiguousGroup(size)
+            })
+        }
+        GrayBuilding::Millstone => explain_by_adjacency(
+            false,
+            board,
+            BuildingType::Gray,
+            &HashSet::from(MILLSTONE_ADJACENT_TYPES),
+        ),
+        GrayBuilding::Shed => {
+            explain_per_each(board, BuildingType::Gray, |_| Reason::Flat)
+        }
+        GrayBuilding::Well => {
+            explain_per_each(board, BuildingType::Gray, |idx| {
+                Reason::AdjacentBlueBuildings(num_adjacent_blue_buildings(
+                    board, idx,
+                ))
+            })
+        }
+    };
+
+    reasons
+}
+
 // =============================================================================
 #[cfg(test)]
 mod test {
@@ -3,16 +3,37 @@ use std::collections::HashMap;
 use crate::board::space::BuildingType;
 use crate::board::Board;
 use crate::building_config::{BuildingConfig, GreenBuilding};
-use crate::score::{score_by_count, score_per_each, ScoringContext};
+use crate::score::explain::Reason;
+use crate::score::{
+    explain_by_count, explain_per_each, score_by_count, score_per_each,
+    ScoringContext,
+};
 
 // -----------------------------------------------------------------------------
+/// Return the number of feast halls in the town of the player to the right,
+/// `other`, or `feast_halls_of_missing_neighbor` without one.
+fn other_feast_hall_count(
+    scoring_context: &ScoringContext,
+    other_opt: Option<&Board>,
+) -> u32 {
+    let other_count = other_opt
+        .map(|other| other.count_building_type(BuildingType::Green))
+        .unwrap_or(scoring_context.feast_halls_of_missing_neighbor);
+
+    other_count
+}
+
+// -----------------------------------------------------------------------------
+/// Score feast halls against the town of the player to the right, `other`.
+/// Without a neighbor, as in a solo game, they are scored against
+/// `feast_halls_of_missing_neighbor` feast halls.
 fn score_feast_halls(
     board: &Board,
     scoring_context: &ScoringContext,
-    other: &Board,
+    other_opt: Option<&Board>,
 ) -> HashMap<usize, i32> {
-    let points = if board.count_building_type(BuildingType::Green)
-        > other.count_building_type(BuildingType::Green)
+    let other_count = other_feast_hall_count(scoring_context, other_opt);
+    let points = if board.count_building_type(BuildingType::Green) > other_count
     {
         scoring_context.points_per_feast_hall_with_greater_count
     } else {
@@ -24,34 +45,34 @@ fn score_feast_halls(
     scores
 }
 
+// -----------------------------------------------------------------------------
+/// Return the number of inns in the row and in the column of space #idx.
+fn inns_in_row_and_col(board: &Board, idx: usize) -> (u32, u32) {
+    let inns = board.building_mask(BuildingType::Green);
+    let inns_in_row = (board.row_mask(board.row(idx)) & inns).len();
+    let inns_in_col = (board.col_mask(board.col(idx)) & inns).len();
+
+    (inns_in_row, inns_in_col)
+}
+
 // -----------------------------------------------------------------------------
 fn score_inns(
     board: &Board,
     scoring_context: &ScoringContext,
 ) -> HashMap<usize, i32> {
-    // Count number of inns in each row and column.
-    let (inns_in_row, inns_in_col) =
-        board.count_building_type_per_row_and_col(BuildingType::Green);
-
     // Score only the inns that are alone in their column and row.
-    let scores = board.spaces().iter().enumerate().fold(
-        HashMap::new(),
-        |mut scores, (idx, space)| {
-            if space.building_type_eq(BuildingType::Green) {
-                let row = board.row(idx);
-                let col = board.col(idx);
-                let points = if inns_in_row.get(&row).copied().unwrap_or(0) == 1
-                    && inns_in_col.get(&col).copied().unwrap_or(0) == 1
-                {
-                    scoring_context.points_per_inn
-                } else {
-                    0
-                };
-                scores.insert(idx, points);
-            }
-            scores
-        },
-    );
+    let scores = board
+        .building_mask(BuildingType::Green)
+        .iter()
+        .map(|idx| {
+            let points = if inns_in_row_and_col(board, idx) == (1, 1) {
+                scoring_context.points_per_inn
+            } else {
+                0
+            };
+            (idx, points)
+        })
+        .collect();
 
     scores
 }
@@ -71,11 +92,7 @@ pub fn score(
             scoring_context.default_score_for_almshouses,
         ),
         GreenBuilding::FeastHall => {
-            if let Some(other) = other_opt {
-                score_feast_halls(board, scoring_context, other)
-            } else {
-                panic!("no second board provided to score feast halls");
-            }
+            score_feast_halls(board, scoring_context, other_opt)
         }
         GreenBuilding::Inn => score_inns(board, scoring_context),
         GreenBuilding::Tavern => score_by_count(
@@ -89,6 +106,36 @@ pub fn score(
     score
 }
 
+// -----------------------------------------------------------------------------
+pub fn explain(
+    board: &Board,
+    building_config: &BuildingConfig,
+    scoring_context: &ScoringContext,
+    other_opt: Option<&Board>,
+) -> HashMap<usize, Reason> {
+    let reasons = match building_config.green() {
+        GreenBuilding::Almshouse | GreenBuilding::Tavern => {
+            explain_by_count(board, BuildingType::Green)
+        }
+        GreenBuilding::FeastHall => {
+            let count = board.count_building_type(BuildingType::Green);
+            let other_count =
+                other_feast_hall_count(scoring_context, other_opt);
+            explain_per_each(board, BuildingType::Green, |_| {
+                Reason::FeastHalls { count, other_count }
+            })
+        }
+        GreenBuilding::Inn => {
+            explain_per_each(board, BuildingType::Green, |idx| {
+                let (row, col) = inns_in_row_and_col(board, idx);
+                Reason::InnsInRowAndCol { row, col }
+            })
+        }
+    };
+
+    reasons
+}
+
 // =============================================================================
 #[cfg(test)]
 mod test {
@@ -104,42 +151,46 @@ mod test {
         let scoring_context = ScoringContext::default();
         let mut board = Board::new(4, 4);
         let mut other = Board::new(4, 4);
-        assert!(score_feast_halls(&board, &scoring_context, &other).is_empty());
+        assert!(score_feast_halls(&board, &scoring_context, Some(&other))
+            .is_empty());
 
         board.place(0, BuildingType::Green);
         let expected = HashMap::from([(0, 3)]);
         assert_eq!(
-            score_feast_halls(&board, &scoring_context, &other),
+            score_feast_halls(&board, &scoring_context, Some(&other)),
             expected
         );
 
         other.place(0, BuildingType::Green);
         let expected = HashMap::from([(0, 2)]);
         assert_eq!(
-            score_feast_halls(&board, &scoring_context, &other),
+            score_feast_halls(&board, &scoring_context, Some(&other)),
             expected
         );
 
         other.place(1, BuildingType::Green);
         let expected = HashMap::from([(0, 2)]);
         assert_eq!(
-            score_feast_halls(&board, &scoring_context, &other),
+            score_feast_halls(&board, &scoring_context, Some(&other)),
             expected
         );
 
         board.place(1, BuildingType::Green);
         let expected = HashMap::from([(0, 2), (1, 2)]);
         assert_eq!(
-            score_feast_halls(&board, &scoring_context, &other),
+            score_feast_halls(&board, &scoring_context, Some(&other)),
             expected
         );
 
         board.place(2, BuildingType::Green);
         let expected = HashMap::from([(0, 3), (1, 3), (2, 3)]);
         assert_eq!(
-            score_feast_halls(&board, &scoring_context, &other),
+            score_feast_halls(&board, &scoring_context, Some(&other)),
             expected
         );
+
+        // Without a neighbor, compare against an empty town.
+        assert_eq!(score_feast_halls(&board, &scoring_context, None), expected);
     }
 
     // -------------------------------------------------------------------------
@@ -219,14 +270,14 @@ mod test {
             RedBuilding::Farm,
             YellowBuilding::Theater,
         );
-        let result = std::panic::catch_unwind(|| {
-            score(&board, &building_config, &scoring_context, None)
-        });
-        assert!(result.is_err());
-
-        let other = Board::new(4, 4);
         let expected =
             HashMap::from([(0, 3), (5, 3), (10, 3), (12, 3), (15, 3)]);
+        assert_eq!(
+            score(&board, &building_config, &scoring_context, None),
+            expected
+        );
+
+        let other = Board::new(4, 4);
         assert_eq!(
             score(&board, &building_config, &scoring_context, Some(&other)),
             expected
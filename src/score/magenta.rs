@@ -1,30 +1,35 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
-use strum::IntoEnumIterator;
+use strum::{EnumCount, IntoEnumIterator};
 
+use crate::board::bitboard::Bitmask;
 use crate::board::space::BuildingType;
 use crate::board::Board;
 use crate::building_config::{BuildingConfig, MagentaBuilding};
-use crate::score::{score_if_in_idx_set, score_per_each, ScoringContext};
+use crate::score::explain::Reason;
+use crate::score::{
+    explain_per_each, score_if_in_mask, score_per_each, ScoringContext,
+};
+
+// -----------------------------------------------------------------------------
+/// Return the number of building types other than magenta in the town.
+fn num_unique_non_magenta_building_types(board: &Board) -> usize {
+    let non_magenta_idxs = board
+        .all_mask()
+        .difference(board.building_mask(BuildingType::Magenta));
+    let num_unique_building_types =
+        board.count_unique_building_types_in_mask(&non_magenta_idxs);
+
+    num_unique_building_types
+}
 
 // -----------------------------------------------------------------------------
 fn score_archive_of_the_second_age(
     board: &Board,
     scoring_context: &ScoringContext,
 ) -> HashMap<usize, i32> {
-    let num_unique_building_types = board
-        .spaces()
-        .iter()
-        .fold(HashSet::new(), |mut unique_building_types, space| {
-            if let Some(building_type) = space.building_type() {
-                if building_type != BuildingType::Magenta {
-                    unique_building_types.insert(building_type);
-                }
-            }
-            unique_building_types
-        })
-        .len();
-
+    let num_unique_building_types =
+        num_unique_non_magenta_building_types(board);
     let scores = score_per_each(
         board,
         BuildingType::Magenta,
@@ -41,45 +46,70 @@ fn score_mandras_palace(
     board: &Board,
     scoring_context: &ScoringContext,
 ) -> HashMap<usize, i32> {
-    let score = board.spaces().iter().enumerate().fold(
-        HashMap::new(),
-        |mut scores, (idx, space)| {
-            if space.building_type_eq(BuildingType::Magenta) {
-                let points = board.unique_adjacent_building_types(idx).len() as i32
-                    * scoring_context.points_per_unique_adjacent_building_type_for_mandras_palace;
-                scores.insert(idx, points);
-            }
-            scores
-        },
-    );
+    let score = board
+        .building_mask(BuildingType::Magenta)
+        .iter()
+        .map(|idx| {
+            let points = board
+                .count_unique_building_types_in_mask(board.adjacent_mask(idx))
+                as i32
+                * scoring_context.points_per_unique_adjacent_building_type_for_mandras_palace;
+            (idx, points)
+        })
+        .collect();
 
     score
 }
 
 // -----------------------------------------------------------------------------
-fn score_silvia_forum(
+/// Score shrines by the number of buildings in the town when they were
+/// constructed. Every shrine must have a recorded history; `try_score` reports
+/// one without it as an error instead of panicking.
+fn score_shrine_of_the_elder_tree(
     board: &Board,
     scoring_context: &ScoringContext,
 ) -> HashMap<usize, i32> {
-    let points = BuildingType::iter()
-        // Vector of HashSets of indices of the largest contiguous group of each
-        // building type.
-        .fold(
-            Vec::new(),
-            |mut max_contiguous_group_sizes, building_type| {
-                let max = board
-                    .contiguous_groups(&HashSet::from([building_type]))
-                    .iter()
-                    .map(|contiguous_group| contiguous_group.len())
-                    .max()
-                    .unwrap_or(0);
-                max_contiguous_group_sizes.push(max);
-                max_contiguous_group_sizes
-            },
-        )
-        .into_iter()
+    let scores = board
+        .building_mask(BuildingType::Magenta)
+        .iter()
+        .map(|idx| {
+            let num_buildings = board
+                .buildings_when_built(idx)
+                .expect("shrine has no record of the buildings when built");
+            let points = scoring_context
+                .points_by_buildings_when_built_for_shrine_of_the_elder_tree
+                .get(&num_buildings)
+                .copied()
+                .unwrap_or(
+                    scoring_context.default_score_for_shrine_of_the_elder_tree,
+                );
+            (idx, points)
+        })
+        .collect();
+
+    scores
+}
+
+// -----------------------------------------------------------------------------
+/// Return the size of the largest contiguous group of buildings of one type.
+fn largest_contiguous_group(board: &Board) -> usize {
+    let largest = BuildingType::iter()
+        .flat_map(|building_type| {
+            board.contiguous_group_masks(board.building_mask(building_type))
+        })
+        .map(|contiguous_group| contiguous_group.len() as usize)
         .max()
-        .unwrap_or(0) as i32
+        .unwrap_or(0);
+
+    largest
+}
+
+// -----------------------------------------------------------------------------
+fn score_silvia_forum(
+    board: &Board,
+    scoring_context: &ScoringContext,
+) -> HashMap<usize, i32> {
+    let points = largest_contiguous_group(board) as i32
         * scoring_context
             .points_per_building_in_largest_contiguous_group_for_silva_forum
         + scoring_context.base_points_per_silva_forum;
@@ -89,16 +119,23 @@ fn score_silvia_forum(
     scores
 }
 
+// -----------------------------------------------------------------------------
+/// Return the number of building types missing from the town.
+fn num_missing_building_types(board: &Board) -> usize {
+    let num_unique_building_types =
+        board.count_unique_building_types_in_mask(board.all_mask());
+    let num_missing_building_types =
+        BuildingType::COUNT - num_unique_building_types;
+
+    num_missing_building_types
+}
+
 // -----------------------------------------------------------------------------
 fn score_the_sky_baths(
     board: &Board,
     scoring_context: &ScoringContext,
 ) -> HashMap<usize, i32> {
-    let idxs = HashSet::from_iter(0..board.elems());
-    let num_unique_building_types =
-        board.unique_building_types_in_idx_set(&idxs).len();
-    let num_missing_building_types =
-        BuildingType::iter().count() - num_unique_building_types;
+    let num_missing_building_types = num_missing_building_types(board);
     let points = num_missing_building_types as i32
         * scoring_context.points_per_missing_building_type_for_the_sky_baths;
 
@@ -107,12 +144,35 @@ fn score_the_sky_baths(
     scores
 }
 
+// -----------------------------------------------------------------------------
+/// Score starlooms by the position in which the town was completed. Towns
+/// that were never completed score nothing.
+fn score_the_starloom(
+    board: &Board,
+    scoring_context: &ScoringContext,
+) -> HashMap<usize, i32> {
+    let points = board
+        .finish_position()
+        .map(|finish_position| {
+            scoring_context
+                .points_by_finish_position_for_the_starloom
+                .get(&finish_position)
+                .copied()
+                .unwrap_or(scoring_context.default_score_for_the_starloom)
+        })
+        .unwrap_or(0);
+
+    let scores = score_per_each(board, BuildingType::Magenta, points);
+
+    scores
+}
+
 // -----------------------------------------------------------------------------
 pub fn score(
     board: &Board,
     building_config: &BuildingConfig,
     scoring_context: &ScoringContext,
-    fed_idxs: &HashSet<usize>,
+    fed_idxs: &Bitmask,
 ) -> HashMap<usize, i32> {
     let scores = match building_config.magenta() {
         MagentaBuilding::ArchitectsGuild => score_per_each(
@@ -123,7 +183,7 @@ pub fn score(
         MagentaBuilding::ArchiveOfTheSecondAge => {
             score_archive_of_the_second_age(board, scoring_context)
         }
-        MagentaBuilding::BarrettCastle => score_if_in_idx_set(
+        MagentaBuilding::BarrettCastle => score_if_in_mask(
             board,
             fed_idxs,
             BuildingType::Magenta,
@@ -167,9 +227,11 @@ pub fn score(
         MagentaBuilding::MandrasPalace => {
             score_mandras_palace(board, scoring_context)
         }
-        MagentaBuilding::ShrineOfTheElderTree
-        | MagentaBuilding::TheStarloom => {
-            score_per_each(board, BuildingType::Magenta, -99)
+        MagentaBuilding::ShrineOfTheElderTree => {
+            score_shrine_of_the_elder_tree(board, scoring_context)
+        }
+        MagentaBuilding::TheStarloom => {
+            score_the_starloom(board, scoring_context)
         }
         MagentaBuilding::SilvaForum => {
             score_silvia_forum(board, scoring_context)
@@ -182,6 +244,46 @@ pub fn score(
     scores
 }
 
+// -----------------------------------------------------------------------------
+pub fn explain(
+    board: &Board,
+    building_config: &BuildingConfig,
+    fed_idxs: &Bitmask,
+) -> HashMap<usize, Reason> {
+    let reason = |idx: usize| match building_config.magenta() {
+        MagentaBuilding::ArchitectsGuild
+        | MagentaBuilding::CathedralOfCaterina
+        | MagentaBuilding::FortIronweed
+        | MagentaBuilding::GrandMausoleumOfTheRodina
+        | MagentaBuilding::ObeliskOfTheCrescent
+        | MagentaBuilding::OpaleyesWatch
+        | MagentaBuilding::StatueOfTheBondmaker
+        | MagentaBuilding::GroveUniversity => Reason::Flat,
+        MagentaBuilding::ArchiveOfTheSecondAge => Reason::UniqueBuildingTypes(
+            num_unique_non_magenta_building_types(board),
+        ),
+        MagentaBuilding::BarrettCastle => Reason::Fed(fed_idxs.contains(idx)),
+        MagentaBuilding::MandrasPalace => Reason::UniqueAdjacentBuildingTypes(
+            board.count_unique_building_types_in_mask(board.adjacent_mask(idx)),
+        ),
+        MagentaBuilding::ShrineOfTheElderTree => {
+            Reason::BuildingsWhenBuilt(board.buildings_when_built(idx))
+        }
+        MagentaBuilding::TheStarloom => {
+            Reason::FinishPosition(board.finish_position())
+        }
+        MagentaBuilding::SilvaForum => {
+            Reason::LargestContiguousGroup(largest_contiguous_group(board))
+        }
+        MagentaBuilding::TheSkyBaths => {
+            Reason::MissingBuildingTypes(num_missing_building_types(board))
+        }
+    };
+    let reasons = explain_per_each(board, BuildingType::Magenta, reason);
+
+    reasons
+}
+
 // =============================================================================
 #[cfg(test)]
 mod test {
@@ -272,6 +374,98 @@ mod test {
         );
     }
 
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_score_shrine_of_the_elder_tree() {
+        let scoring_context = ScoringContext::default();
+        let mut board = Board::new(4, 4);
+        assert!(
+            score_shrine_of_the_elder_tree(&board, &scoring_context).is_empty()
+        );
+
+        board.place(0, BuildingType::Magenta);
+        board.record_buildings_when_built(0);
+        assert_eq!(
+            score_shrine_of_the_elder_tree(&board, &scoring_context),
+            HashMap::from([(0, 1)])
+        );
+
+        for idx in 1..5 {
+            board.place(idx, BuildingType::Blue);
+        }
+        board.place(0, BuildingType::Magenta);
+        board.record_buildings_when_built(0);
+        assert_eq!(
+            score_shrine_of_the_elder_tree(&board, &scoring_context),
+            HashMap::from([(0, 5)])
+        );
+
+        board.place(5, BuildingType::Blue);
+        board.place(0, BuildingType::Magenta);
+        board.record_buildings_when_built(0);
+        assert_eq!(
+            score_shrine_of_the_elder_tree(&board, &scoring_context),
+            HashMap::from([(0, 8)])
+        );
+
+        for idx in 6..12 {
+            board.place(idx, BuildingType::Blue);
+        }
+        board.place(0, BuildingType::Magenta);
+        board.record_buildings_when_built(0);
+        assert_eq!(
+            score_shrine_of_the_elder_tree(&board, &scoring_context),
+            HashMap::from([(0, 8)])
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    #[should_panic]
+    fn test_score_shrine_of_the_elder_tree_without_history() {
+        let mut board = Board::new(4, 4);
+        board.place(0, BuildingType::Magenta);
+        score_shrine_of_the_elder_tree(&board, &ScoringContext::default());
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_score_the_starloom() {
+        let scoring_context = ScoringContext::default();
+        let mut board = Board::new(4, 4);
+        assert!(score_the_starloom(&board, &scoring_context).is_empty());
+
+        board.place(0, BuildingType::Magenta);
+        assert_eq!(
+            score_the_starloom(&board, &scoring_context),
+            HashMap::from([(0, 0)])
+        );
+
+        board.set_finish_position(Some(1));
+        assert_eq!(
+            score_the_starloom(&board, &scoring_context),
+            HashMap::from([(0, 6)])
+        );
+
+        board.set_finish_position(Some(2));
+        assert_eq!(
+            score_the_starloom(&board, &scoring_context),
+            HashMap::from([(0, 3)])
+        );
+
+        board.set_finish_position(Some(3));
+        assert_eq!(
+            score_the_starloom(&board, &scoring_context),
+            HashMap::from([(0, 2)])
+        );
+
+        board.set_finish_position(Some(4));
+        assert_eq!(
+            score_the_starloom(&board, &scoring_context),
+            HashMap::from([(0, 0)])
+        );
+    }
+
     // -------------------------------------------------------------------------
     #[test]
     fn test_score_silva_forum() {
@@ -1,19 +1,28 @@
 use std::collections::{HashMap, HashSet};
 
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use crate::board::bitboard::Bitmask;
 use crate::board::space::BuildingType;
 use crate::board::Board;
-use crate::building_config::{BuildingConfig, MagentaBuilding};
+use crate::building_config::{BuildingConfig, MagentaBuilding, YellowBuilding};
+use crate::error::Error;
+use crate::score::explain::Reason;
 use crate::score::feed::feed;
 
 pub mod black;
 pub mod blue;
+pub mod explain;
 pub mod feed;
 pub mod gray;
 pub mod green;
 pub mod magenta;
 pub mod orange;
+pub mod standings;
 pub mod yellow;
 
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct ScoringContext {
     points_per_tailor_in_center: i32,
     base_points_per_tailor: i32,
@@ -27,6 +36,7 @@ pub struct ScoringContext {
     points_per_factory: i32,
     points_per_feast_hall_with_equal_or_lesser_count: i32,
     points_per_feast_hall_with_greater_count: i32,
+    feast_halls_of_missing_neighbor: u32,
     points_per_fed_blue_building_for_chapels: i32,
     points_per_fountain: i32,
     points_per_inn: i32,
@@ -54,6 +64,11 @@ pub struct ScoringContext {
     base_points_per_silva_forum: i32,
     points_per_building_in_largest_contiguous_group_for_silva_forum: i32,
     points_per_missing_building_type_for_the_sky_baths: i32,
+    points_by_buildings_when_built_for_shrine_of_the_elder_tree:
+        HashMap<u32, i32>,
+    default_score_for_shrine_of_the_elder_tree: i32,
+    points_by_finish_position_for_the_starloom: HashMap<usize, i32>,
+    default_score_for_the_starloom: i32,
     adjacent_building_types_for_abbeys: HashSet<BuildingType>,
     points_per_cloister_in_corner: i32,
     equivalent_num_of_blue_buildings_for_barrett_castle: u32,
@@ -91,6 +106,7 @@ impl ScoringContext {
             points_per_factory: 0,
             points_per_feast_hall_with_equal_or_lesser_count: 2,
             points_per_feast_hall_with_greater_count: 3,
+            feast_halls_of_missing_neighbor: 0,
             points_per_fed_blue_building_for_chapels: 1,
             points_per_fountain: 2,
             points_per_inn: 3,
@@ -118,6 +134,15 @@ impl ScoringContext {
             base_points_per_silva_forum: 1,
             points_per_building_in_largest_contiguous_group_for_silva_forum: 1,
             points_per_missing_building_type_for_the_sky_baths: 2,
+            points_by_buildings_when_built_for_shrine_of_the_elder_tree:
+                HashMap::from([(1, 1), (2, 2), (3, 3), (4, 4), (5, 5)]),
+            default_score_for_shrine_of_the_elder_tree: 8,
+            points_by_finish_position_for_the_starloom: HashMap::from([
+                (1, 6),
+                (2, 3),
+                (3, 2),
+            ]),
+            default_score_for_the_starloom: 0,
             adjacent_building_types_for_abbeys: HashSet::from([
                 BuildingType::Black,
                 BuildingType::Green,
@@ -134,10 +159,34 @@ impl ScoringContext {
             points_per_unused_space: -1,
         }
     }
+
+    // -------------------------------------------------------------------------
+    /// Return an error if a points-by-count table does not score zero
+    /// buildings as zero points, which `score_by_count` relies on.
+    pub fn validate(&self) -> Result<(), Error> {
+        let tables = [
+            (
+                "points_by_count_for_almshouses",
+                &self.points_by_count_for_almshouses,
+            ),
+            (
+                "points_by_count_for_taverns",
+                &self.points_by_count_for_taverns,
+            ),
+        ];
+        for (name, points_by_count) in tables {
+            if points_by_count.get(&0) != Some(&0) {
+                return Err(Error::InvalidScoringTable(name.to_string()));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 // =============================================================================
 /// A `ScoreCard`.
+#[derive(Deserialize, Serialize)]
 pub struct ScoreCard {
     black: HashMap<usize, i32>,
     blue: HashMap<usize, i32>,
@@ -237,6 +286,84 @@ impl ScoreCard {
     }
 }
 
+// -----------------------------------------------------------------------------
+/// Return the building types in `building_types` in a fixed order.
+fn ordered(building_types: &HashSet<BuildingType>) -> Vec<BuildingType> {
+    let ordered = BuildingType::iter()
+        .filter(|building_type| building_types.contains(building_type))
+        .collect();
+
+    ordered
+}
+
+// -----------------------------------------------------------------------------
+/// Explain the scores from `score_by_adjacency` by the building types in
+/// `adjacent_types` that each building is adjacent to.
+fn explain_by_adjacency(
+    is_disjoint: bool,
+    board: &Board,
+    building_type: BuildingType,
+    adjacent_types: &HashSet<BuildingType>,
+) -> HashMap<usize, Reason> {
+    let reasons = explain_per_each(board, building_type, |idx| {
+        let wanted = ordered(adjacent_types);
+        let found = ordered(&board.unique_building_types_in_mask(
+            &(board.adjacent_mask(idx)
+                & board.building_types_mask(adjacent_types)),
+        ));
+        if is_disjoint {
+            Reason::NotAdjacentTo {
+                forbidden: wanted,
+                found,
+            }
+        } else {
+            Reason::AdjacentTo { wanted, found }
+        }
+    });
+
+    reasons
+}
+
+// -----------------------------------------------------------------------------
+/// Explain the scores from `score_by_count`: the first `building_type`
+/// building scores for the count, and the rest were counted by it.
+fn explain_by_count(
+    board: &Board,
+    building_type: BuildingType,
+) -> HashMap<usize, Reason> {
+    let count = board.count_building_type(building_type);
+    let first_idx = board.building_mask(building_type).first();
+    let reasons = explain_per_each(board, building_type, |idx| {
+        if Some(idx) == first_idx {
+            Reason::BuildingCount(count)
+        } else {
+            Reason::CountedAt(first_idx.unwrap())
+        }
+    });
+
+    reasons
+}
+
+// -----------------------------------------------------------------------------
+/// Explain the score of each `building_type` building by calling `reason` with
+/// its index.
+fn explain_per_each<F>(
+    board: &Board,
+    building_type: BuildingType,
+    reason: F,
+) -> HashMap<usize, Reason>
+where
+    F: Fn(usize) -> Reason,
+{
+    let reasons = board
+        .building_mask(building_type)
+        .iter()
+        .map(|idx| (idx, reason(idx)))
+        .collect();
+
+    reasons
+}
+
 // -----------------------------------------------------------------------------
 /// Score buildings of a given type based on adjacency to the building types
 /// in `adjacent_types`. If `is_disjoint` is `false`, buildings of type
@@ -249,24 +376,22 @@ fn score_by_adjacency(
     adjacent_types: &HashSet<BuildingType>,
     points: i32,
 ) -> HashMap<usize, i32> {
-    let scores = board.spaces().iter().enumerate().fold(
-        HashMap::new(),
-        |mut m, (idx, space)| {
-            if space.building_type_eq(building_type) {
-                let points_scored = if board
-                    .unique_adjacent_building_types(idx)
-                    .is_disjoint(&adjacent_types)
-                    == is_disjoint
+    let adjacent_mask = board.building_types_mask(adjacent_types);
+    let scores = board
+        .building_mask(building_type)
+        .iter()
+        .map(|idx| {
+            let points_scored =
+                if board.adjacent_mask(idx).intersects(&adjacent_mask)
+                    != is_disjoint
                 {
                     points
                 } else {
                     0
                 };
-                m.insert(idx, points_scored);
-            }
-            m
-        },
-    );
+            (idx, points_scored)
+        })
+        .collect();
 
     scores
 }
@@ -285,19 +410,17 @@ fn score_by_count(
     assert_eq!(points_by_count.get(&0).copied().unwrap(), 0);
     let count = board.count_building_type(building_type);
     let points = *points_by_count.get(&count).unwrap_or(&default);
-    let scores = board.spaces().iter().enumerate().fold(
-        HashMap::new(),
-        |mut m, (idx, space)| {
-            if space.building_type_eq(building_type) {
-                if m.len() == 0 {
-                    m.insert(idx, points);
-                } else {
-                    m.insert(idx, 0);
-                }
+    let mask = board.building_mask(building_type);
+    let scores = mask
+        .iter()
+        .map(|idx| {
+            if Some(idx) == mask.first() {
+                (idx, points)
+            } else {
+                (idx, 0)
             }
-            m
-        },
-    );
+        })
+        .collect();
 
     scores
 }
@@ -318,26 +441,24 @@ fn score_if_adjacent_to(
 }
 
 // -----------------------------------------------------------------------------
-/// Score each `building_type` building if `idxs` contains its index.
-fn score_if_in_idx_set(
+/// Score each `building_type` building if `mask` contains its index.
+fn score_if_in_mask(
     board: &Board,
-    idxs: &HashSet<usize>,
+    mask: &Bitmask,
     building_type: BuildingType,
     points: i32,
 ) -> HashMap<usize, i32> {
-    let scores = board.spaces().iter().enumerate().fold(
-        HashMap::new(),
-        |mut m, (idx, space)| {
-            if space.building_type_eq(building_type) {
-                if idxs.contains(&idx) {
-                    m.insert(idx, points);
-                } else {
-                    m.insert(idx, 0);
-                }
+    let scores = board
+        .building_mask(building_type)
+        .iter()
+        .map(|idx| {
+            if mask.contains(idx) {
+                (idx, points)
+            } else {
+                (idx, 0)
             }
-            m
-        },
-    );
+        })
+        .collect();
 
     scores
 }
@@ -364,15 +485,11 @@ fn score_per_each(
     building_type: BuildingType,
     points: i32,
 ) -> HashMap<usize, i32> {
-    let scores = board.spaces().iter().enumerate().fold(
-        HashMap::new(),
-        |mut m, (idx, space)| {
-            if space.building_type_eq(building_type) {
-                m.insert(idx, points);
-            }
-            m
-        },
-    );
+    let scores = board
+        .building_mask(building_type)
+        .iter()
+        .map(|idx| (idx, points))
+        .collect();
 
     scores
 }
@@ -393,19 +510,42 @@ fn score_unused_spaces(
         scoring_context.points_per_unused_space
     };
 
-    let scores = board.spaces().iter().enumerate().fold(
-        HashMap::new(),
-        |mut scores, (idx, space)| {
-            if space.is_unused() {
-                scores.insert(idx, points);
-            }
-            scores
-        },
-    );
+    let scores = board
+        .unused_mask()
+        .iter()
+        .map(|idx| (idx, points))
+        .collect();
 
     scores
 }
 
+// -----------------------------------------------------------------------------
+/// Return the seat of the player to the right of `player`, or `None` in a solo
+/// game. Players are seated clockwise in index order, so the player to the
+/// right is the one before.
+pub fn right_neighbor(player: usize, num_players: usize) -> Option<usize> {
+    try_right_neighbor(player, num_players).unwrap()
+}
+
+// -----------------------------------------------------------------------------
+/// Return the seat of the player to the right of `player` like
+/// `right_neighbor`, or an error if there is no such player.
+pub fn try_right_neighbor(
+    player: usize,
+    num_players: usize,
+) -> Result<Option<usize>, Error> {
+    if player >= num_players {
+        return Err(Error::NoSuchPlayer(player));
+    }
+    let neighbor = if num_players > 1 {
+        Some((player + num_players - 1) % num_players)
+    } else {
+        None
+    };
+
+    Ok(neighbor)
+}
+
 // -----------------------------------------------------------------------------
 pub fn score(
     board: &Board,
@@ -439,11 +579,130 @@ pub fn score(
     score_card
 }
 
+// -----------------------------------------------------------------------------
+/// Score a town like `score`, but return an error instead of panicking if the
+/// scoring tables are invalid, Tailors are scored on a board without a center,
+/// or a Shrine of the Elder Tree has no history.
+pub fn try_score(
+    board: &Board,
+    building_config: &BuildingConfig,
+    scoring_context: &ScoringContext,
+    other: Option<&Board>,
+) -> Result<ScoreCard, Error> {
+    scoring_context.validate()?;
+    if building_config.yellow() == YellowBuilding::Tailor {
+        board.try_center_mask()?;
+    }
+    if building_config.magenta() == MagentaBuilding::ShrineOfTheElderTree {
+        if let Some(idx) = board
+            .building_mask(BuildingType::Magenta)
+            .iter()
+            .find(|idx| board.buildings_when_built(*idx).is_none())
+        {
+            return Err(Error::NoBuildingsWhenBuilt(idx));
+        }
+    }
+    let score_card = score(board, building_config, scoring_context, other);
+
+    Ok(score_card)
+}
+
+// -----------------------------------------------------------------------------
+/// Explain why each space in the town scored what `score` gives it. Every
+/// space in the flattened `ScoreCard` has a reason, and no others do.
+pub fn explain(
+    board: &Board,
+    building_config: &BuildingConfig,
+    scoring_context: &ScoringContext,
+    other: Option<&Board>,
+) -> HashMap<usize, Reason> {
+    let fed_idxs = feed(board, building_config, scoring_context);
+    let unused_mask = board.unused_mask();
+    let unused = unused_mask.iter().map(|idx| (idx, Reason::Unused));
+    let reasons = black::explain(board, building_config)
+        .into_iter()
+        .chain(blue::explain(board, building_config, &fed_idxs))
+        .chain(gray::explain(board, building_config))
+        .chain(green::explain(
+            board,
+            building_config,
+            scoring_context,
+            other,
+        ))
+        .chain(magenta::explain(board, building_config, &fed_idxs))
+        .chain(orange::explain(
+            board,
+            building_config,
+            scoring_context,
+            &fed_idxs,
+        ))
+        .chain(explain_per_each(board, BuildingType::Red, |_| {
+            Reason::Feeds
+        }))
+        .chain(yellow::explain(board, building_config, scoring_context))
+        .chain(unused)
+        .collect();
+
+    reasons
+}
+
+// -----------------------------------------------------------------------------
+/// Score every player's town, in seating order, each with the player's own
+/// config. Feast Halls are scored against the town of the player to the right.
+pub fn score_players(
+    boards: &[&Board],
+    building_configs: &[&BuildingConfig],
+    scoring_context: &ScoringContext,
+) -> Vec<ScoreCard> {
+    assert_eq!(boards.len(), building_configs.len());
+    let score_cards = boards
+        .iter()
+        .zip(building_configs.iter())
+        .enumerate()
+        .map(|(player, (board, building_config))| {
+            let other = right_neighbor(player, boards.len())
+                .map(|neighbor| boards[neighbor]);
+            score(board, building_config, scoring_context, other)
+        })
+        .collect();
+
+    score_cards
+}
+
+// -----------------------------------------------------------------------------
+/// Score every player's town like `score_players`, but with `try_score`, and
+/// return an error if there is not exactly one config for each town.
+pub fn try_score_players(
+    boards: &[&Board],
+    building_configs: &[&BuildingConfig],
+    scoring_context: &ScoringContext,
+) -> Result<Vec<ScoreCard>, Error> {
+    if building_configs.len() != boards.len() {
+        return Err(Error::WrongNumberOfConfigs {
+            expected: boards.len(),
+            found: building_configs.len(),
+        });
+    }
+    let score_cards = boards
+        .iter()
+        .zip(building_configs.iter())
+        .enumerate()
+        .map(|(player, (board, building_config))| {
+            let other = right_neighbor(player, boards.len())
+                .map(|neighbor| boards[neighbor]);
+            try_score(board, building_config, scoring_context, other)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(score_cards)
+}
+
 // =============================================================================
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::board::space::Resource;
+    use crate::building_config::generate::Card;
     use crate::building_config::{
         BlackBuilding, BlueBuilding, GrayBuilding, GreenBuilding,
         MagentaBuilding, OrangeBuilding, RedBuilding, YellowBuilding,
@@ -640,29 +899,29 @@ mod test {
 
     // -------------------------------------------------------------------------
     #[test]
-    fn test_score_if_in_idx_set() {
+    fn test_score_if_in_mask() {
         let mut board = Board::new(4, 4);
-        let mut idxs: HashSet<usize> = HashSet::new();
+        let mut mask = Bitmask::EMPTY;
         let building_type = BuildingType::Yellow;
         let points = 4;
 
-        let result = score_if_in_idx_set(&board, &idxs, building_type, points);
+        let result = score_if_in_mask(&board, &mask, building_type, points);
         assert!(result.is_empty());
 
         board.place(0, BuildingType::Red);
-        let result = score_if_in_idx_set(&board, &idxs, building_type, points);
+        let result = score_if_in_mask(&board, &mask, building_type, points);
         assert!(result.is_empty());
 
         board.place(1, BuildingType::Yellow);
-        let result = score_if_in_idx_set(&board, &idxs, building_type, points);
+        let result = score_if_in_mask(&board, &mask, building_type, points);
         assert_eq!(result, HashMap::from([(1, 0)]));
 
-        idxs.insert(0);
-        let result = score_if_in_idx_set(&board, &idxs, building_type, points);
+        mask.insert(0);
+        let result = score_if_in_mask(&board, &mask, building_type, points);
         assert_eq!(result, HashMap::from([(1, 0)]));
 
-        idxs.insert(1);
-        let result = score_if_in_idx_set(&board, &idxs, building_type, points);
+        mask.insert(1);
+        let result = score_if_in_mask(&board, &mask, building_type, points);
         assert_eq!(result, HashMap::from([(1, 4)]));
     }
 
@@ -819,4 +1078,244 @@ mod test {
     #[test]
     #[ignore]
     fn test_score() {}
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_right_neighbor() {
+        assert_eq!(right_neighbor(0, 1), None);
+        assert_eq!(right_neighbor(0, 2), Some(1));
+        assert_eq!(right_neighbor(1, 2), Some(0));
+        assert_eq!(right_neighbor(0, 4), Some(3));
+        assert_eq!(right_neighbor(3, 4), Some(2));
+        assert_eq!(try_right_neighbor(4, 4), Err(Error::NoSuchPlayer(4)));
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_score_players() {
+        let scoring_context = ScoringContext::default();
+        let building_config = BuildingConfig::new(
+            BlackBuilding::Factory,
+            BlueBuilding::Cottage,
+            GrayBuilding::Millstone,
+            GreenBuilding::FeastHall,
+            MagentaBuilding::OpaleyesWatch,
+            OrangeBuilding::Abbey,
+            RedBuilding::Farm,
+            YellowBuilding::Theater,
+        );
+
+        // Three players with one, two and three feast halls.
+        let mut boards = [Board::new(4, 4), Board::new(4, 4), Board::new(4, 4)];
+        for (player, board) in boards.iter_mut().enumerate() {
+            for idx in 0..=player {
+                board.place(idx, BuildingType::Green);
+            }
+        }
+        let boards: Vec<&Board> = boards.iter().collect();
+        let building_configs = vec![&building_config; 3];
+        let score_cards =
+            score_players(&boards, &building_configs, &scoring_context);
+        let green: Vec<i32> =
+            score_cards.iter().map(|card| card.score_green()).collect();
+        // Player #0 is compared against player #2, who has more feast halls.
+        assert_eq!(green, vec![2, 6, 9]);
+
+        // Solo.
+        let score_cards = score_players(
+            &boards[..1],
+            &building_configs[..1],
+            &scoring_context,
+        );
+        assert_eq!(score_cards[0].score_green(), 3);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_score_players_with_own_monuments() {
+        let scoring_context = ScoringContext::default();
+        let building_config = BuildingConfig::new(
+            BlackBuilding::Factory,
+            BlueBuilding::Cottage,
+            GrayBuilding::Millstone,
+            GreenBuilding::Tavern,
+            MagentaBuilding::OpaleyesWatch,
+            OrangeBuilding::Abbey,
+            RedBuilding::Farm,
+            YellowBuilding::Theater,
+        );
+        let fort = building_config.with_magenta(MagentaBuilding::FortIronweed);
+        let cathedral =
+            building_config.with_magenta(MagentaBuilding::CathedralOfCaterina);
+
+        let mut board = Board::new(4, 4);
+        board.place(0, BuildingType::Magenta);
+        let boards = vec![&board, &board];
+        let score_cards =
+            score_players(&boards, &[&fort, &cathedral], &scoring_context);
+        assert_eq!(score_cards[0].score_magenta(), 7);
+        assert_eq!(score_cards[0].score_unused(), -15);
+        assert_eq!(score_cards[1].score_magenta(), 2);
+        assert_eq!(score_cards[1].score_unused(), 0);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_try_score_players() {
+        let scoring_context = ScoringContext::default();
+        let building_config = BuildingConfig::for_test();
+        let boards = [Board::new(4, 4), Board::new(4, 4)];
+        let boards: Vec<&Board> = boards.iter().collect();
+        assert_eq!(
+            try_score_players(&boards, &[&building_config], &scoring_context)
+                .err(),
+            Some(Error::WrongNumberOfConfigs {
+                expected: 2,
+                found: 1
+            })
+        );
+        let score_cards = try_score_players(
+            &boards,
+            &[&building_config, &building_config],
+            &scoring_context,
+        )
+        .unwrap();
+        assert_eq!(score_cards.len(), 2);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_try_score() {
+        let building_config = BuildingConfig::for_test()
+            .with_card(Card::Black(BlackBuilding::Factory))
+            .with_card(Card::Gray(GrayBuilding::Millstone))
+            .with_card(Card::Magenta(MagentaBuilding::OpaleyesWatch))
+            .with_card(Card::Orange(OrangeBuilding::Abbey));
+        let mut scoring_context = ScoringContext::default();
+        let board = Board::new(4, 4);
+        assert!(
+            try_score(&board, &building_config, &scoring_context, None).is_ok()
+        );
+
+        // Tailors need a center to score.
+        let board = Board::new(5, 4);
+        assert_eq!(
+            try_score(&board, &building_config, &scoring_context, None).err(),
+            Some(Error::NoCenter { rows: 5, cols: 4 })
+        );
+        let theater =
+            building_config.with_card(Card::Yellow(YellowBuilding::Theater));
+        assert!(try_score(&board, &theater, &scoring_context, None).is_ok());
+
+        scoring_context.points_by_count_for_taverns.insert(0, 1);
+        assert_eq!(
+            try_score(&board, &theater, &scoring_context, None).err(),
+            Some(Error::InvalidScoringTable(
+                "points_by_count_for_taverns".to_string()
+            ))
+        );
+
+        // A shrine must have a history to score.
+        let shrine =
+            theater.with_magenta(MagentaBuilding::ShrineOfTheElderTree);
+        let scoring_context = ScoringContext::default();
+        let mut board = Board::new(4, 4);
+        board.place(0, BuildingType::Blue);
+        board.place(1, BuildingType::Magenta);
+        assert_eq!(
+            try_score(&board, &shrine, &scoring_context, None).err(),
+            Some(Error::NoBuildingsWhenBuilt(1))
+        );
+        board.record_buildings_when_built(1);
+        assert_eq!(
+            try_score(&board, &shrine, &scoring_context, None)
+                .unwrap()
+                .score_magenta(),
+            2
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_explain() {
+        let scoring_context = ScoringContext::default();
+        let building_config = BuildingConfig::for_test()
+            .with_card(Card::Orange(OrangeBuilding::Temple));
+        let board = Board::from_notation(
+            "
+            R B O .
+            B . . .
+            B B G G
+            M{4} w . K[b]
+            ",
+        )
+        .unwrap();
+        let reasons = explain(&board, &building_config, &scoring_context, None);
+        assert_eq!(reasons[&0], Reason::Feeds);
+        assert_eq!(reasons[&1], Reason::Fed(true));
+        assert_eq!(
+            reasons[&2],
+            Reason::AdjacentFedBlueBuildings {
+                count: 1,
+                needed: 2
+            }
+        );
+        assert_eq!(reasons[&3], Reason::Unused);
+        assert_eq!(reasons[&10], Reason::BuildingCount(2));
+        assert_eq!(reasons[&11], Reason::CountedAt(10));
+        assert_eq!(reasons[&12], Reason::LargestContiguousGroup(3));
+        assert_eq!(reasons[&13], Reason::Unused);
+        assert_eq!(reasons[&15], Reason::ResourcesStored(1));
+
+        // Every scored space has a reason, for every card in every deck.
+        for i in 0..MagentaBuilding::iter().count() {
+            let building_config = BuildingConfig::new(
+                BlackBuilding::iter().cycle().nth(i).unwrap(),
+                BlueBuilding::iter().cycle().nth(i).unwrap(),
+                GrayBuilding::iter().cycle().nth(i).unwrap(),
+                GreenBuilding::iter().cycle().nth(i).unwrap(),
+                MagentaBuilding::iter().nth(i).unwrap(),
+                OrangeBuilding::iter().cycle().nth(i).unwrap(),
+                RedBuilding::iter().cycle().nth(i).unwrap(),
+                YellowBuilding::iter().cycle().nth(i).unwrap(),
+            );
+            let score_card =
+                score(&board, &building_config, &scoring_context, None);
+            let reasons =
+                explain(&board, &building_config, &scoring_context, None);
+            let mut scored: Vec<usize> =
+                score_card.flatten().into_keys().collect();
+            let mut explained: Vec<usize> = reasons.into_keys().collect();
+            scored.sort();
+            explained.sort();
+            assert_eq!(explained, scored);
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_explain_unused() {
+        let scoring_context = ScoringContext::default();
+        let building_config = BuildingConfig::for_test()
+            .with_card(Card::Orange(OrangeBuilding::Temple));
+        let board = Board::from_notation(
+            "
+            . s . .
+            . B . .
+            . . . .
+            . . . g
+            ",
+        )
+        .unwrap();
+
+        // A resource left on a space does not make it a used one.
+        let reasons = explain(&board, &building_config, &scoring_context, None);
+        assert_eq!(reasons[&0], Reason::Unused);
+        assert_eq!(reasons[&1], Reason::Unused);
+        assert_eq!(reasons[&15], Reason::Unused);
+        assert_eq!(reasons[&5], Reason::Fed(false));
+        let score_card =
+            score(&board, &building_config, &scoring_context, None);
+        assert_eq!(score_card.flatten()[&1], -1);
+    }
 }
@@ -1,80 +1,115 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
+use crate::board::bitboard::Bitmask;
 use crate::board::space::BuildingType;
 use crate::board::Board;
 use crate::building_config::{BuildingConfig, MagentaBuilding, OrangeBuilding};
-use crate::score::{score_if_not_adjacent_to, score_per_each, ScoringContext};
+use crate::score::explain::Reason;
+use crate::score::{
+    explain_by_adjacency, explain_per_each, score_if_not_adjacent_to,
+    score_per_each, ScoringContext,
+};
+
+// -----------------------------------------------------------------------------
+/// Return the space with Barrett Castle on it, if it is in town.
+fn barrett_castle_mask(
+    board: &Board,
+    building_config: &BuildingConfig,
+) -> Bitmask {
+    if building_config.magenta() == MagentaBuilding::BarrettCastle {
+        board.building_mask(BuildingType::Magenta).clone()
+    } else {
+        Bitmask::EMPTY
+    }
+}
+
+// -----------------------------------------------------------------------------
+/// Return the number of fed blue buildings in the town, counting a fed Barrett
+/// Castle as two.
+fn num_fed_blue_buildings(
+    board: &Board,
+    building_config: &BuildingConfig,
+    fed_idxs: &Bitmask,
+) -> i32 {
+    let num_fed_blue_buildings =
+        (fed_idxs & board.building_mask(BuildingType::Blue)).len() as i32
+            + 2 * (fed_idxs & barrett_castle_mask(board, building_config)).len()
+                as i32;
+
+    num_fed_blue_buildings
+}
 
 // -----------------------------------------------------------------------------
 fn score_chapels(
     board: &Board,
     building_config: &BuildingConfig,
     scoring_context: &ScoringContext,
-    fed_idxs: &HashSet<usize>,
+    fed_idxs: &Bitmask,
 ) -> HashMap<usize, i32> {
-    let num_fed_blue_buildings =
-        fed_idxs.iter().fold(0, |num_fed_blue_buildings, idx| {
-            let space = &board.spaces()[*idx];
-            if space.building_type_eq(BuildingType::Blue) {
-                num_fed_blue_buildings + 1
-            } else if space.building_type_eq(BuildingType::Magenta)
-                && building_config.magenta() == MagentaBuilding::BarrettCastle
-            {
-                num_fed_blue_buildings + 2
-            } else {
-                num_fed_blue_buildings
-            }
-        });
-    let points = num_fed_blue_buildings
+    let points = num_fed_blue_buildings(board, building_config, fed_idxs)
         * scoring_context.points_per_fed_blue_building_for_chapels;
     let scores = score_per_each(board, BuildingType::Orange, points);
 
     scores
 }
 
+// -----------------------------------------------------------------------------
+fn num_cloisters_in_corners(board: &Board) -> i32 {
+    let num_cloisters = (board.corner_mask()
+        & board.building_mask(BuildingType::Orange))
+    .len() as i32;
+
+    num_cloisters
+}
+
 // -----------------------------------------------------------------------------
 fn score_cloisters(
     board: &Board,
     scoring_context: &ScoringContext,
 ) -> HashMap<usize, i32> {
-    let points = board.corner_idxs().into_iter().fold(0, |points, idx| {
-        let space = &board.spaces()[idx];
-        if space.building_type_eq(BuildingType::Orange) {
-            points + scoring_context.points_per_cloister_in_corner
-        } else {
-            points
-        }
-    });
+    let points = num_cloisters_in_corners(board)
+        * scoring_context.points_per_cloister_in_corner;
 
     let scores = score_per_each(board, BuildingType::Orange, points);
 
     scores
 }
 
+// -----------------------------------------------------------------------------
+/// Return the number of fed blue buildings adjacent to the temple at `idx`,
+/// counting a fed Barrett Castle as several.
+fn num_adjacent_fed_blue_buildings(
+    board: &Board,
+    building_config: &BuildingConfig,
+    scoring_context: &ScoringContext,
+    fed_idxs: &Bitmask,
+    idx: usize,
+) -> u32 {
+    let adjacent_fed_idxs = board.adjacent_mask(idx) & fed_idxs;
+    let num_adjacent_blue_buildings =
+        (&adjacent_fed_idxs & board.building_mask(BuildingType::Blue)).len()
+            + (adjacent_fed_idxs & barrett_castle_mask(board, building_config))
+                .len()
+                * scoring_context
+                    .equivalent_num_of_blue_buildings_for_barrett_castle;
+
+    num_adjacent_blue_buildings
+}
+
 // -----------------------------------------------------------------------------
 fn score_temple(
     board: &Board,
     building_config: &BuildingConfig,
     scoring_context: &ScoringContext,
-    fed_idxs: &HashSet<usize>,
+    fed_idxs: &Bitmask,
     idx: usize,
 ) -> bool {
-    let score = board.adjacent_idxs(idx).intersection(fed_idxs).fold(
-        0,
-        |num_adjacent_blue_buildings, ii| {
-            let space = &board.spaces()[*ii];
-            if space.building_type_eq(BuildingType::Blue) {
-                num_adjacent_blue_buildings + 1
-            } else if space.building_type_eq(BuildingType::Magenta)
-                && building_config.magenta() == MagentaBuilding::BarrettCastle
-            {
-                num_adjacent_blue_buildings
-                    + scoring_context
-                        .equivalent_num_of_blue_buildings_for_barrett_castle
-            } else {
-                num_adjacent_blue_buildings
-            }
-        },
+    let score = num_adjacent_fed_blue_buildings(
+        board,
+        building_config,
+        scoring_context,
+        fed_idxs,
+        idx,
     ) >= scoring_context
         .min_adjacent_blue_buildings_to_score_temple;
 
@@ -86,27 +121,25 @@ fn score_temples(
     board: &Board,
     building_config: &BuildingConfig,
     scoring_context: &ScoringContext,
-    fed_idxs: &HashSet<usize>,
+    fed_idxs: &Bitmask,
 ) -> HashMap<usize, i32> {
-    let scores = board.spaces().iter().enumerate().fold(
-        HashMap::new(),
-        |mut scores, (idx, space)| {
-            if space.building_type_eq(BuildingType::Orange) {
-                if score_temple(
-                    board,
-                    building_config,
-                    scoring_context,
-                    fed_idxs,
-                    idx,
-                ) {
-                    scores.insert(idx, scoring_context.points_per_temple);
-                } else {
-                    scores.insert(idx, 0);
-                }
+    let scores = board
+        .building_mask(BuildingType::Orange)
+        .iter()
+        .map(|idx| {
+            if score_temple(
+                board,
+                building_config,
+                scoring_context,
+                fed_idxs,
+                idx,
+            ) {
+                (idx, scoring_context.points_per_temple)
+            } else {
+                (idx, 0)
             }
-            scores
-        },
-    );
+        })
+        .collect();
 
     scores
 }
@@ -116,7 +149,7 @@ pub fn score(
     board: &Board,
     building_config: &BuildingConfig,
     scoring_context: &ScoringContext,
-    fed_idxs: &HashSet<usize>,
+    fed_idxs: &Bitmask,
 ) -> HashMap<usize, i32> {
     let scores = match building_config.orange() {
         OrangeBuilding::Abbey => score_if_not_adjacent_to(
@@ -137,6 +170,53 @@ pub fn score(
     scores
 }
 
+// -----------------------------------------------------------------------------
+pub fn explain(
+    board: &Board,
+    building_config: &BuildingConfig,
+    scoring_context: &ScoringContext,
+    fed_idxs: &Bitmask,
+) -> HashMap<usize, Reason> {
+    let reasons = match building_config.orange() {
+        OrangeBuilding::Abbey => explain_by_adjacency(
+            true,
+            board,
+            BuildingType::Orange,
+            &scoring_context.adjacent_building_types_for_abbeys,
+        ),
+        OrangeBuilding::Chapel => {
+            let count =
+                num_fed_blue_buildings(board, building_config, fed_idxs);
+            explain_per_each(board, BuildingType::Orange, |_| {
+                Reason::FedBlueBuildings(count)
+            })
+        }
+        OrangeBuilding::Cloister => {
+            let count = num_cloisters_in_corners(board);
+            explain_per_each(board, BuildingType::Orange, |_| {
+                Reason::CloistersInCorners(count)
+            })
+        }
+        OrangeBuilding::Temple => {
+            explain_per_each(board, BuildingType::Orange, |idx| {
+                Reason::AdjacentFedBlueBuildings {
+                    count: num_adjacent_fed_blue_buildings(
+                        board,
+                        building_config,
+                        scoring_context,
+                        fed_idxs,
+                        idx,
+                    ),
+                    needed: scoring_context
+                        .min_adjacent_blue_buildings_to_score_temple,
+                }
+            })
+        }
+    };
+
+    reasons
+}
+
 // =============================================================================
 #[cfg(test)]
 mod test {
@@ -162,7 +242,7 @@ mod test {
             RedBuilding::Farm,
             YellowBuilding::Theater,
         );
-        let mut fed_idxs = HashSet::new();
+        let mut fed_idxs = Bitmask::EMPTY;
 
         board.place(0, BuildingType::Orange);
         let expected = HashMap::from([(0, 0)]);
@@ -327,7 +407,7 @@ mod test {
             RedBuilding::Farm,
             YellowBuilding::Theater,
         );
-        let mut fed_idxs: HashSet<usize> = HashSet::new();
+        let mut fed_idxs = Bitmask::EMPTY;
         board.place(0, BuildingType::Orange);
         assert!(!score_temple(
             &board,
@@ -447,7 +527,7 @@ mod test {
             RedBuilding::Farm,
             YellowBuilding::Theater,
         );
-        let mut fed_idxs: HashSet<usize> = HashSet::new();
+        let mut fed_idxs = Bitmask::EMPTY;
         board.place(0, BuildingType::Orange);
         let expected = HashMap::from([(0, 0)]);
         assert_eq!(
@@ -587,7 +667,7 @@ mod test {
         board.place(15, BuildingType::Red);
         board.place(7, BuildingType::Black);
         board.place(14, BuildingType::Blue);
-        let fed_idxs = HashSet::from([1, 4, 14]);
+        let fed_idxs = Bitmask::from_iter([1, 4, 14]);
 
         // Score with abbeys.
         let building_config = BuildingConfig::new(
@@ -0,0 +1,247 @@
+use std::cmp::Reverse;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::board::Board;
+use crate::score::ScoreCard;
+
+// =============================================================================
+/// One player's line in the final standings.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Standing {
+    player: usize,
+    rank: usize,
+    score: i32,
+    unused_spaces: usize,
+}
+
+impl Standing {
+    pub fn player(&self) -> usize {
+        self.player
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the 1-based rank. Players that are still tied after the
+    /// tie-breaker share a rank.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn score(&self) -> i32 {
+        self.score
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn unused_spaces(&self) -> usize {
+        self.unused_spaces
+    }
+
+    // -------------------------------------------------------------------------
+    /// The key the standings are sorted by: highest score first, then fewest
+    /// unused spaces.
+    fn sort_key(&self) -> (Reverse<i32>, usize) {
+        (Reverse(self.score), self.unused_spaces)
+    }
+}
+
+// =============================================================================
+/// The final standings of a game, from first place to last.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Standings {
+    standings: Vec<Standing>,
+}
+
+impl Standings {
+    pub fn standings(&self) -> &Vec<Standing> {
+        &self.standings
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the players sharing first place.
+    pub fn winners(&self) -> Vec<usize> {
+        let winners = self
+            .standings
+            .iter()
+            .filter(|standing| standing.rank == 1)
+            .map(|standing| standing.player)
+            .collect();
+
+        winners
+    }
+}
+
+impl fmt::Display for Standings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Rank  Player  Score  Unused")?;
+        for standing in self.standings.iter() {
+            writeln!(
+                f,
+                "{:>4}  {:>6}  {:>5}  {:>6}",
+                standing.rank,
+                standing.player,
+                standing.score,
+                standing.unused_spaces
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+/// Rank every player by total score. Ties are broken in favour of the player
+/// with the fewest unused spaces in their town.
+pub fn rank(boards: &[&Board], score_cards: &[ScoreCard]) -> Standings {
+    assert_eq!(boards.len(), score_cards.len());
+
+    let mut standings: Vec<Standing> = boards
+        .iter()
+        .zip(score_cards.iter())
+        .enumerate()
+        .map(|(player, (board, score_card))| Standing {
+            player,
+            rank: 0,
+            score: score_card.score_all(),
+            unused_spaces: board.unused_mask().len() as usize,
+        })
+        .collect();
+    standings.sort_by_key(|standing| (standing.sort_key(), standing.player));
+
+    let sort_keys: Vec<(Reverse<i32>, usize)> = standings
+        .iter()
+        .map(|standing| standing.sort_key())
+        .collect();
+    for standing in standings.iter_mut() {
+        let num_ahead = sort_keys
+            .iter()
+            .filter(|sort_key| **sort_key < standing.sort_key())
+            .count();
+        standing.rank = num_ahead + 1;
+    }
+
+    Standings { standings }
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::space::{BuildingType, Resource};
+    use crate::building_config::{
+        BlackBuilding, BlueBuilding, BuildingConfig, GrayBuilding,
+        GreenBuilding, MagentaBuilding, OrangeBuilding, RedBuilding,
+        YellowBuilding,
+    };
+    use crate::score::{score, ScoringContext};
+
+    // -------------------------------------------------------------------------
+    /// Sheds score one point each wherever they are, which makes scores easy
+    /// to line up.
+    fn shed_config() -> BuildingConfig {
+        BuildingConfig::new(
+            BlackBuilding::Factory,
+            BlueBuilding::Cottage,
+            GrayBuilding::Shed,
+            GreenBuilding::Tavern,
+            MagentaBuilding::SilvaForum,
+            OrangeBuilding::Abbey,
+            RedBuilding::Farm,
+            YellowBuilding::Theater,
+        )
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_rank() {
+        let building_config = shed_config();
+        let scoring_context = ScoringContext::default();
+
+        // Player #0: 15 sheds and an empty space scores 14.
+        let mut first = Board::new(4, 4);
+        for idx in 1..16 {
+            first.place(idx, BuildingType::Gray);
+        }
+
+        // Player #1: 14 sheds and two empty spaces scores 12.
+        let mut second = Board::new(4, 4);
+        for idx in 2..16 {
+            second.place(idx, BuildingType::Gray);
+        }
+
+        // Player #2: the same score and unused spaces as player #1.
+        let mut third = Board::new(4, 4);
+        third.place(0, BuildingType::Gray);
+        for idx in 3..16 {
+            third.place(idx, BuildingType::Gray);
+        }
+
+        let boards = vec![&first, &second, &third];
+        let score_cards: Vec<ScoreCard> = boards
+            .iter()
+            .map(|board| score(board, &building_config, &scoring_context, None))
+            .collect();
+        let standings = rank(&boards, &score_cards);
+        let ranked: Vec<(usize, usize, i32, usize)> = standings
+            .standings()
+            .iter()
+            .map(|s| (s.player(), s.rank(), s.score(), s.unused_spaces()))
+            .collect();
+        assert_eq!(ranked, vec![(0, 1, 14, 1), (1, 2, 12, 2), (2, 2, 12, 2)]);
+        assert_eq!(standings.winners(), vec![0]);
+
+        // Fewer unused spaces break the tie: 13 sheds, two factories and an
+        // empty space also score 12.
+        let mut fourth = Board::new(4, 4);
+        for idx in 0..13 {
+            fourth.place(idx, BuildingType::Gray);
+        }
+        fourth.place(13, (BuildingType::Black, Resource::Wood));
+        fourth.place(14, (BuildingType::Black, Resource::Glass));
+        let boards = vec![&second, &fourth, &first];
+        let score_cards: Vec<ScoreCard> = boards
+            .iter()
+            .map(|board| score(board, &building_config, &scoring_context, None))
+            .collect();
+        let standings = rank(&boards, &score_cards);
+        let ranked: Vec<(usize, usize)> = standings
+            .standings()
+            .iter()
+            .map(|s| (s.player(), s.rank()))
+            .collect();
+        assert_eq!(ranked, vec![(2, 1), (1, 2), (0, 3)]);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_display() {
+        let mut board = Board::new(4, 4);
+        board.place(0, BuildingType::Gray);
+        let building_config = shed_config();
+        let score_card =
+            score(&board, &building_config, &ScoringContext::default(), None);
+        let standings = rank(&[&board], &[score_card]);
+        assert_eq!(
+            standings.to_string(),
+            "Rank  Player  Score  Unused\n   1       0    -14      15\n"
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_round_trip() {
+        let mut board = Board::new(4, 4);
+        board.place(0, BuildingType::Gray);
+        let building_config = shed_config();
+        let score_card =
+            score(&board, &building_config, &ScoringContext::default(), None);
+        let standings = rank(&[&board], &[score_card]);
+
+        let json = serde_json::to_string(&standings).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Standings>(&json).unwrap(),
+            standings
+        );
+    }
+}
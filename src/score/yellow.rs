@@ -1,53 +1,67 @@
 use std::cmp;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 use crate::board::space::BuildingType;
 use crate::board::Board;
 use crate::building_config::{BuildingConfig, YellowBuilding};
-use crate::score::{score_if_adjacent_to, score_per_each, ScoringContext};
+use crate::score::explain::Reason;
+use crate::score::{
+    explain_by_adjacency, explain_per_each, score_if_adjacent_to,
+    score_per_each, ScoringContext,
+};
+
+// -----------------------------------------------------------------------------
+/// Return the number of markets in the row or column of each market,
+/// whichever has more.
+fn count_markets_in_row_or_col(board: &Board) -> HashMap<usize, u32> {
+    let markets = board.building_mask(BuildingType::Yellow);
+    let counts = markets
+        .iter()
+        .map(|idx| {
+            let count = cmp::max(
+                (board.row_mask(board.row(idx)) & markets).len(),
+                (board.col_mask(board.col(idx)) & markets).len(),
+            );
+            (idx, count)
+        })
+        .collect();
+
+    counts
+}
 
 // -----------------------------------------------------------------------------
 fn score_markets(
     board: &Board,
     scoring_context: &ScoringContext,
 ) -> HashMap<usize, i32> {
-    // Count the number of markets in each row and column.
-    let (markets_per_row, markets_per_col) =
-        board.count_building_type_per_row_and_col(BuildingType::Yellow);
-
     // Score each market.
-    let scores = board.spaces().iter().enumerate().fold(
-        HashMap::new(),
-        |mut scores, (idx, space)| {
-            if space.building_type_eq(BuildingType::Yellow) {
-                let points = cmp::max(
-                    markets_per_row.get(&board.row(idx)).copied().unwrap_or(0),
-                    markets_per_col.get(&board.col(idx)).copied().unwrap_or(0),
-                ) as i32
-                    * scoring_context.points_per_yellow_building_for_markets;
-                scores.insert(idx, points);
-            }
-            scores
-        },
-    );
+    let scores = count_markets_in_row_or_col(board)
+        .into_iter()
+        .map(|(idx, count)| {
+            let points = count as i32
+                * scoring_context.points_per_yellow_building_for_markets;
+            (idx, points)
+        })
+        .collect();
 
     scores
 }
 
+// -----------------------------------------------------------------------------
+fn num_tailors_in_center(board: &Board) -> i32 {
+    let num_tailors_in_center = (board.center_mask()
+        & board.building_mask(BuildingType::Yellow))
+    .len() as i32;
+
+    num_tailors_in_center
+}
+
 // -----------------------------------------------------------------------------
 fn score_tailors(
     board: &Board,
     scoring_context: &ScoringContext,
 ) -> HashMap<usize, i32> {
-    let num_tailors_in_center =
-        board.center_idxs().into_iter().fold(0, |n, idx| {
-            let space = &board.spaces()[idx];
-            if space.building_type_eq(BuildingType::Yellow) {
-                n + 1
-            } else {
-                n
-            }
-        });
+    let num_tailors_in_center = num_tailors_in_center(board);
     let points = scoring_context.points_per_tailor_in_center
         * num_tailors_in_center
         + scoring_context.base_points_per_tailor;
@@ -56,53 +70,42 @@ fn score_tailors(
     scores
 }
 
+// -----------------------------------------------------------------------------
+/// Return the number of unique building types other than theaters in the row
+/// and column of each theater.
+fn count_unique_building_types_for_theaters(
+    board: &Board,
+) -> HashMap<usize, usize> {
+    let theaters = board.building_mask(BuildingType::Yellow);
+    let counts = theaters
+        .iter()
+        .map(|idx| {
+            let row_and_col =
+                board.row_mask(board.row(idx)) | board.col_mask(board.col(idx));
+            let count = board.count_unique_building_types_in_mask(
+                &row_and_col.difference(theaters),
+            );
+            (idx, count)
+        })
+        .collect();
+
+    counts
+}
+
 // -----------------------------------------------------------------------------
 fn score_theaters(
     board: &Board,
     scoring_context: &ScoringContext,
 ) -> HashMap<usize, i32> {
-    // Create sets of unique building types in each row and column.
-    let (unique_building_types_per_row, unique_building_types_per_col) =
-        board.spaces().iter().enumerate().fold(
-            (HashMap::new(), HashMap::new()),
-            |(mut rows, mut cols), (idx, space)| {
-                if let Some(building_type) = space.building_type() {
-                    if building_type != BuildingType::Yellow {
-                        rows.entry(board.row(idx))
-                            .or_insert(HashSet::new())
-                            .insert(building_type);
-                        cols.entry(board.col(idx))
-                            .or_insert(HashSet::new())
-                            .insert(building_type);
-                    }
-                }
-                (rows, cols)
-            },
-        );
-
     // Score each theater.
-    let scores = board.spaces().iter().enumerate().fold(
-        HashMap::new(),
-        |mut scores, (idx, space)| {
-            if space.building_type_eq(BuildingType::Yellow) {
-                let empty_set = HashSet::new();
-                let unique_building_types_in_row =
-                    unique_building_types_per_row
-                        .get(&board.row(idx))
-                        .unwrap_or(&empty_set);
-                let unique_building_types_in_col =
-                    unique_building_types_per_col
-                        .get(&board.col(idx))
-                        .unwrap_or(&empty_set);
-                let points = unique_building_types_in_row
-                    .union(unique_building_types_in_col)
-                    .count() as i32
-                    * scoring_context.points_per_unique_type_for_theaters;
-                scores.insert(idx, points);
-            }
-            scores
-        },
-    );
+    let scores = count_unique_building_types_for_theaters(board)
+        .into_iter()
+        .map(|(idx, count)| {
+            let points = count as i32
+                * scoring_context.points_per_unique_type_for_theaters;
+            (idx, points)
+        })
+        .collect();
 
     scores
 }
@@ -128,6 +131,42 @@ pub fn score(
     scores
 }
 
+// -----------------------------------------------------------------------------
+pub fn explain(
+    board: &Board,
+    building_config: &BuildingConfig,
+    scoring_context: &ScoringContext,
+) -> HashMap<usize, Reason> {
+    let reasons = match building_config.yellow() {
+        YellowBuilding::Bakery => explain_by_adjacency(
+            false,
+            board,
+            BuildingType::Yellow,
+            &scoring_context.adjacent_building_types_for_bakeries,
+        ),
+        YellowBuilding::Market => count_markets_in_row_or_col(board)
+            .into_iter()
+            .map(|(idx, count)| (idx, Reason::MarketsInRowOrCol(count)))
+            .collect(),
+        YellowBuilding::Tailor => {
+            let num_tailors_in_center = num_tailors_in_center(board);
+            explain_per_each(board, BuildingType::Yellow, |_| {
+                Reason::TailorsInCenter(num_tailors_in_center)
+            })
+        }
+        YellowBuilding::Theater => {
+            count_unique_building_types_for_theaters(board)
+                .into_iter()
+                .map(|(idx, count)| {
+                    (idx, Reason::UniqueBuildingTypesInRowAndCol(count))
+                })
+                .collect()
+        }
+    };
+
+    reasons
+}
+
 // =============================================================================
 #[cfg(test)]
 mod test {
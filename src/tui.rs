@@ -0,0 +1,360 @@
+use std::fs;
+use std::io::{self, Write};
+
+use colored::Colorize;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
+};
+use crossterm::style::Print;
+use crossterm::terminal::{
+    self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use crossterm::{execute, queue};
+
+use crate::board::notation::{building_type_from_symbol, resource_from_symbol};
+use crate::board::space::{BuildingType, Resource, Space};
+use crate::board::Board;
+use crate::building_config::{BuildingConfig, WAREHOUSE_CAPACITY};
+use crate::display::{colorize, make_messages};
+use crate::score::{try_score, ScoringContext};
+
+/// The width of a cell on the screen, not counting its borders.
+const CELL_WIDTH: usize = 7;
+
+const HELP: &str = "arrows: move   b g s h w: resource   \
+K B A G M O R Y: building   .: clear   ctrl-s: save   q: quit";
+
+// =============================================================================
+/// The state of the interactive editor for a single town.
+pub struct App {
+    board: Board,
+    building_config: BuildingConfig,
+    scoring_context: ScoringContext,
+    cursor: usize,
+    path: Option<String>,
+    status: String,
+    quit: bool,
+}
+
+impl App {
+    /// Edit `board`, saving it in board notation to `path` if given.
+    pub fn new(
+        board: Board,
+        building_config: BuildingConfig,
+        path: Option<String>,
+    ) -> Self {
+        Self {
+            board,
+            building_config,
+            scoring_context: ScoringContext::default(),
+            cursor: 0,
+            path,
+            status: String::new(),
+            quit: false,
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    // -------------------------------------------------------------------------
+    /// Move the cursor by `d_row` rows and `d_col` columns, stopping at the
+    /// edges of the board.
+    pub fn move_cursor(&mut self, d_row: isize, d_col: isize) {
+        let clamp = |n: usize, d: isize, len: usize| -> usize {
+            n.saturating_add_signed(d).min(len - 1)
+        };
+        let row = clamp(self.board.row(self.cursor), d_row, self.board.rows());
+        let col = clamp(self.board.col(self.cursor), d_col, self.board.cols());
+        self.cursor = self.board.idx(row, col);
+    }
+
+    // -------------------------------------------------------------------------
+    /// Put a resource on the space under the cursor. A resource put on a
+    /// Warehouse is stored there, and one put on a Factory or Bank is assigned
+    /// to it.
+    fn put_resource(&mut self, resource: Resource) {
+        let space = match &self.board.spaces()[self.cursor] {
+            Space::BuildingWithResources(building_type, resources) => {
+                if resources.len() >= WAREHOUSE_CAPACITY {
+                    self.status = String::from("the warehouse is full");
+                    return;
+                }
+                let mut resources = resources.clone();
+                resources.push(resource);
+                Space::BuildingWithResources(*building_type, resources)
+            }
+            Space::BuildingWithOptResource(building_type, _) => {
+                Space::BuildingWithOptResource(*building_type, Some(resource))
+            }
+            _ => Space::Resource(resource),
+        };
+        self.board.place(self.cursor, space);
+    }
+
+    // -------------------------------------------------------------------------
+    fn save(&mut self) {
+        let path = self.path.clone().unwrap_or(String::from("town.txt"));
+        self.status = match fs::write(&path, self.board.to_notation() + "\n") {
+            Ok(()) => format!("saved to {path}"),
+            Err(err) => format!("cannot save to {path}: {err}"),
+        };
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return `true` once the user has asked to quit.
+    pub fn is_done(&self) -> bool {
+        self.quit
+    }
+
+    // -------------------------------------------------------------------------
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        self.status.clear();
+        match key.code {
+            KeyCode::Char('s')
+                if key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                self.save()
+            }
+            KeyCode::Char('c')
+                if key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                self.quit = true
+            }
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Up => self.move_cursor(-1, 0),
+            KeyCode::Down => self.move_cursor(1, 0),
+            KeyCode::Left => self.move_cursor(0, -1),
+            KeyCode::Right => self.move_cursor(0, 1),
+            KeyCode::Char('.') | KeyCode::Delete | KeyCode::Backspace => {
+                self.board.remove(self.cursor)
+            }
+            KeyCode::Char(symbol) => {
+                if let Some(resource) = resource_from_symbol(symbol) {
+                    self.put_resource(resource);
+                } else if let Some(building_type) =
+                    building_type_from_symbol(symbol)
+                {
+                    let space =
+                        self.building_config.new_building_space(building_type);
+                    self.board.place(self.cursor, space);
+                    // A monument placed here is taken to be built now, so the
+                    // Shrine of the Elder Tree has a history to score.
+                    if building_type == BuildingType::Magenta {
+                        self.board.record_buildings_when_built(self.cursor);
+                    }
+                } else {
+                    self.status = format!("'{symbol}' does nothing");
+                }
+            }
+            _ => (),
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    /// Return the lines of the screen: the board with the score of each
+    /// building under it, and the score of each color beside it.
+    pub fn render(&self) -> Vec<String> {
+        let score_result = try_score(
+            &self.board,
+            &self.building_config,
+            &self.scoring_context,
+            None,
+        );
+        let mut messages = match &score_result {
+            Ok(score_card) => {
+                let mut messages =
+                    make_messages(score_card, &self.building_config);
+                messages
+                    .push(format!("Total score: {}", score_card.score_all()));
+                messages
+            }
+            Err(err) => vec![format!("Cannot score: {err}")],
+        };
+        messages.extend(
+            self.board
+                .validate(&self.building_config)
+                .iter()
+                .map(|violation| format!("{}", violation.to_string().red())),
+        );
+        let scores = score_result.map(|score_card| score_card.flatten()).ok();
+
+        let horizontal_line =
+            "-".repeat(self.board.cols() * (CELL_WIDTH + 1) + 1);
+        let mut board_lines = vec![horizontal_line.clone()];
+        for row in 0..self.board.rows() {
+            let mut symbols = String::new();
+            let mut points = String::new();
+            for col in 0..self.board.cols() {
+                let idx = self.board.idx(row, col);
+                let space = &self.board.spaces()[idx];
+                let symbol = format!(
+                    "{:^width$}",
+                    space.to_notation(),
+                    width = CELL_WIDTH
+                );
+                let symbol = match space.building_type() {
+                    Some(building_type) => colorize(building_type, &symbol),
+                    None => symbol.normal(),
+                };
+                let symbol = if idx == self.cursor {
+                    symbol.reversed()
+                } else {
+                    symbol
+                };
+                let point = scores
+                    .as_ref()
+                    .and_then(|scores| scores.get(&idx))
+                    .map(|point| point.to_string())
+                    .unwrap_or_default();
+                symbols += &format!("|{symbol}");
+                points += &format!("|{point:^width$}", width = CELL_WIDTH);
+            }
+            board_lines.push(symbols + "|");
+            board_lines.push(points + "|");
+            board_lines.push(horizontal_line.clone());
+        }
+
+        let num_lines = board_lines.len().max(messages.len());
+        messages.resize(num_lines, String::new());
+        let blank = " ".repeat(horizontal_line.len());
+        let mut lines: Vec<String> = (0..num_lines)
+            .map(|i| {
+                let board_line = board_lines.get(i).unwrap_or(&blank);
+                format!("{board_line}  {}", messages[i])
+            })
+            .collect();
+        lines.push(String::new());
+        lines.push(self.status.clone());
+        lines.push(String::from(HELP));
+
+        lines
+    }
+}
+
+// -----------------------------------------------------------------------------
+fn event_loop<W: Write>(app: &mut App, out: &mut W) -> io::Result<()> {
+    while !app.is_done() {
+        queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+        for line in app.render() {
+            queue!(out, Print(line), Print("\r\n"))?;
+        }
+        out.flush()?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                app.handle_key(key);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+/// Run the editor full-screen until the user quits.
+pub fn run(mut app: App) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, EnterAlternateScreen, Hide)?;
+    let result = event_loop(&mut app, &mut stdout);
+    execute!(stdout, Show, LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+
+    result
+}
+
+// =============================================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // -------------------------------------------------------------------------
+    fn new_app() -> App {
+        let building_config = BuildingConfig::for_test();
+
+        App::new(Board::new(4, 4), building_config, None)
+    }
+
+    // -------------------------------------------------------------------------
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_move_cursor() {
+        let mut app = new_app();
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Left);
+        assert_eq!(app.cursor(), 0);
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.cursor(), 9);
+        app.move_cursor(10, 10);
+        assert_eq!(app.cursor(), 15);
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_place() {
+        let mut app = new_app();
+        press(&mut app, KeyCode::Char('w'));
+        assert_eq!(app.board().spaces()[0], Space::Resource(Resource::Wood));
+        press(&mut app, KeyCode::Char('B'));
+        assert_eq!(
+            app.board().spaces()[0],
+            Space::Building(BuildingType::Blue)
+        );
+        press(&mut app, KeyCode::Char('.'));
+        assert_eq!(app.board().spaces()[0], Space::Empty);
+        press(&mut app, KeyCode::Char('M'));
+        assert_eq!(app.board().buildings_when_built(0), Some(1));
+        press(&mut app, KeyCode::Char('.'));
+
+        // Resources put on a Warehouse are stored there.
+        press(&mut app, KeyCode::Char('K'));
+        for _ in 0..WAREHOUSE_CAPACITY {
+            press(&mut app, KeyCode::Char('g'));
+        }
+        assert_eq!(
+            app.board().spaces()[0],
+            Space::BuildingWithResources(
+                BuildingType::Black,
+                vec![Resource::Glass; WAREHOUSE_CAPACITY]
+            )
+        );
+        press(&mut app, KeyCode::Char('b'));
+        assert_eq!(app.status(), "the warehouse is full");
+
+        press(&mut app, KeyCode::Char('x'));
+        assert_eq!(app.status(), "'x' does nothing");
+        assert!(!app.is_done());
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.is_done());
+    }
+
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_render() {
+        let mut app = new_app();
+        press(&mut app, KeyCode::Char('B'));
+        let lines = app.render();
+        assert!(lines.iter().any(|line| line.contains("Total score: -15")));
+        assert_eq!(lines.last().unwrap(), HELP);
+    }
+}